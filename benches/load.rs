@@ -0,0 +1,58 @@
+//! Compares loading a small env only config with `load` and `load_env_fast`, in a small
+//! environment and in one with many unrelated variables
+//!
+//! Run with `cargo bench --bench load`.
+
+// `criterion_group!` generates an undocumented public function
+#![expect(missing_docs)]
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use konfik::{ConfigLoader, Konfik};
+use std::hint::black_box;
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct FunctionConfig {
+    region: String,
+    table: String,
+    #[serde(default)]
+    timeout: u32,
+}
+
+#[expect(unsafe_code)]
+fn load(c: &mut Criterion) {
+    // SAFETY: the benchmark is single threaded and sets the vars before loading
+    unsafe {
+        std::env::set_var("BENCH_REGION", "eu-west-1");
+        std::env::set_var("BENCH_TABLE", "sessions");
+        std::env::set_var("BENCH_TIMEOUT", "30");
+    }
+
+    let loader = ConfigLoader::default().with_env_prefix("BENCH");
+    bench_group(c, "env only", &loader);
+
+    // SAFETY: see above
+    unsafe {
+        for i in 0..500 {
+            std::env::set_var(
+                format!("UNRELATED_VAR_{i}"),
+                "some value of an unrelated var",
+            );
+        }
+    }
+    bench_group(c, "env only, 500 unrelated vars", &loader);
+}
+
+fn bench_group(c: &mut Criterion, name: &str, loader: &ConfigLoader) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("load", |b| {
+        b.iter(|| black_box(loader.load::<FunctionConfig>()));
+    });
+    group.bench_function("load_env_fast", |b| {
+        b.iter(|| black_box(loader.load_env_fast::<FunctionConfig>()));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, load);
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+//! Loads a config with 500 fields from env vars, in a small environment and in one with
+//! many unrelated variables
+//!
+//! Run with `cargo bench --bench wide_env`.
+
+// `criterion_group!` generates an undocumented public function
+#![expect(missing_docs)]
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use konfik::{
+    ConfigLoader,
+    config_meta::{ConfigMeta, FieldMeta, FieldType},
+};
+use serde_json::{Map, Value};
+use std::hint::black_box;
+
+const FIELDS: usize = 500;
+
+/// Config with the fields `field_0` to `field_499`
+#[derive(serde::Deserialize)]
+#[serde(transparent)]
+struct WideConfig(#[expect(dead_code)] Map<String, Value>);
+
+impl ConfigMeta for WideConfig {
+    fn config_metadata() -> Vec<FieldMeta> {
+        (0..FIELDS)
+            .map(|i| {
+                let name: &'static str = format!("field_{i}").leak();
+                let mut field = FieldMeta::new(name, FieldType::Scalar("String"));
+                field.has_default = true;
+                field
+            })
+            .collect()
+    }
+}
+
+#[expect(unsafe_code)]
+fn wide_env(c: &mut Criterion) {
+    // SAFETY: the benchmark is single threaded and sets the vars before loading
+    unsafe {
+        for i in (0..FIELDS).step_by(2) {
+            std::env::set_var(format!("WIDE_FIELD_{i}"), "value");
+        }
+    }
+
+    let loader = ConfigLoader::default().with_env_prefix("WIDE");
+    let mut group = c.benchmark_group("500 fields");
+    group.bench_function("load", |b| {
+        b.iter(|| black_box(loader.load::<WideConfig>()));
+    });
+
+    // SAFETY: see above
+    unsafe {
+        for i in 0..500 {
+            std::env::set_var(
+                format!("UNRELATED_VAR_{i}"),
+                "some value of an unrelated var",
+            );
+        }
+    }
+    group.bench_function("load, 500 unrelated vars", |b| {
+        b.iter(|| black_box(loader.load::<WideConfig>()));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, wide_env);
+criterion_main!(benches);
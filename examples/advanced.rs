@@ -37,10 +37,9 @@ fn main() {
             if let Some(port) = config
                 .get("port")
                 .and_then(serde_json::value::Value::as_u64)
+                && port > 65535
             {
-                if port > 65535 {
-                    return Err(Error::Validation("Invalid port".to_string()));
-                }
+                return Err(Error::Validation("Invalid port".to_string()));
             }
             Ok(())
         })
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 kingananas20
 
-use syn::{Field, Type, TypePath};
+use syn::{Field, Type, TypePath, meta::ParseNestedMeta};
 
 /// Analysis result for a field
 #[expect(clippy::struct_excessive_bools)]
@@ -10,6 +10,20 @@ pub struct FieldAnalysis {
     pub required: bool,
     pub has_default: bool,
     pub nested: bool,
+    pub positional: bool,
+    pub subcommand: bool,
+    pub cli_skip: bool,
+    pub size: bool,
+    pub deprecated_aliases: Vec<String>,
+    pub default_if_none: Option<String>,
+    pub null_uses_default: bool,
+    pub env: Vec<String>,
+    pub cli: Option<String>,
+    pub transform: Option<syn::Path>,
+    pub precedence: Option<syn::Ident>,
+    pub regex: Option<syn::LitStr>,
+    pub default_fn: Option<syn::Path>,
+    pub eval_default: bool,
 }
 
 /// Analyze a field to determine its requirements
@@ -19,19 +33,26 @@ pub fn analyze_field(field: &Field) -> Result<FieldAnalysis, syn::Error> {
         required: false,
         has_default: false,
         nested: false,
+        positional: false,
+        subcommand: false,
+        cli_skip: false,
+        size: false,
+        deprecated_aliases: Vec::new(),
+        default_if_none: None,
+        null_uses_default: false,
+        env: Vec::new(),
+        cli: None,
+        transform: None,
+        precedence: None,
+        regex: None,
+        default_fn: None,
+        eval_default: false,
     };
 
     for attr in &field.attrs {
         // handle #[konfik(...)]
         if attr.path().is_ident("konfik") {
-            attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("skip") {
-                    analysis.skip = true;
-                } else if meta.path.is_ident("nested") {
-                    analysis.nested = true;
-                }
-                Ok(())
-            })?;
+            attr.parse_nested_meta(|meta| parse_konfik_meta(&mut analysis, &meta))?;
         }
 
         // handle #[command(...)]
@@ -39,8 +60,20 @@ pub fn analyze_field(field: &Field) -> Result<FieldAnalysis, syn::Error> {
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("flatten") {
                     analysis.nested = true;
+                } else if meta.path.is_ident("subcommand") {
+                    analysis.subcommand = true;
                 }
-                Ok(())
+                skip_meta_value(&meta)
+            })?;
+        }
+
+        // handle #[arg(...)]
+        if attr.path().is_ident("arg") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("index") {
+                    analysis.positional = true;
+                }
+                skip_meta_value(&meta)
             })?;
         }
 
@@ -53,6 +86,13 @@ pub fn analyze_field(field: &Field) -> Result<FieldAnalysis, syn::Error> {
                 } else if meta.path.is_ident("default") {
                     // `default` can appear as `default` or `default = "..."`; either way we mark has_default
                     analysis.has_default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        let default_fn = meta.value()?.parse::<syn::LitStr>()?;
+                        analysis.default_fn = Some(default_fn.parse()?);
+                    }
+                } else {
+                    // consume values like `deserialize_with = "..."` of items we don't care about
+                    skip_meta_value(&meta)?;
                 }
                 // return Ok(()) to continue parsing other nested items
                 Ok(())
@@ -60,20 +100,129 @@ pub fn analyze_field(field: &Field) -> Result<FieldAnalysis, syn::Error> {
         }
     }
 
-    // keep your original semantics: required if not Option<T> and no default
-    analysis.required = !is_option_type(&field.ty) && !analysis.has_default;
+    if analysis.eval_default && analysis.default_fn.is_none() {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`eval_default` needs a default function, e.g. `#[serde(default = \"path\")]`",
+        ));
+    }
+
+    if analysis.default_if_none.is_some() && !is_option_type(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`default_if_none` only applies to `Option` fields, use `#[serde(default)]` instead",
+        ));
+    }
+
+    // required if not Option<T> and no default, skipped fields never come from the config
+    analysis.required = !analysis.skip && !is_option_type(&field.ty) && !analysis.has_default;
 
     Ok(analysis)
 }
 
+/// Consumes the `= value` or `(...)` part of a nested meta item we don't care about
+fn skip_meta_value(meta: &ParseNestedMeta) -> Result<(), syn::Error> {
+    if meta.input.peek(syn::Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<proc_macro2::TokenStream>()?;
+    }
+    Ok(())
+}
+
 /// Check if a type is Option<T>
 fn is_option_type(ty: &Type) -> bool {
-    if let Type::Path(TypePath { path, .. }) = ty {
-        if let Some(segment) = path.segments.last() {
-            if segment.ident == "Option" {
-                return true;
-            }
-        }
+    if let Type::Path(TypePath { path, .. }) = ty
+        && let Some(segment) = path.segments.last()
+        && segment.ident == "Option"
+    {
+        return true;
     }
     false
 }
+
+/// Parses one item of `#[konfik(...)]`
+fn parse_konfik_meta(
+    analysis: &mut FieldAnalysis,
+    meta: &ParseNestedMeta,
+) -> Result<(), syn::Error> {
+    if meta.path.is_ident("skip") {
+        analysis.skip = true;
+    } else if meta.path.is_ident("nested") {
+        analysis.nested = true;
+    } else if meta.path.is_ident("positional") {
+        analysis.positional = true;
+    } else if meta.path.is_ident("cli_skip") {
+        analysis.cli_skip = true;
+    } else if meta.path.is_ident("size") {
+        analysis.size = true;
+    } else if meta.path.is_ident("deprecated_alias") {
+        let alias = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.deprecated_aliases.push(alias.value());
+    } else if meta.path.is_ident("default_if_none") {
+        let default = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.default_if_none = Some(default.value());
+    } else if meta.path.is_ident("null_uses_default") {
+        analysis.null_uses_default = true;
+    } else if meta.path.is_ident("eval_default") {
+        analysis.eval_default = true;
+    } else if meta.path.is_ident("env") {
+        let env = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.env.push(env.value());
+    } else if meta.path.is_ident("cli") {
+        let cli = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.cli = Some(cli.value());
+    } else if meta.path.is_ident("transform") {
+        let transform = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.transform = Some(transform.parse()?);
+    } else if meta.path.is_ident("precedence") {
+        let precedence = meta.value()?.parse::<syn::LitStr>()?;
+        analysis.precedence = Some(parse_precedence(&precedence)?);
+    } else if meta.path.is_ident("regex") {
+        let regex = meta.value()?.parse::<syn::LitStr>()?;
+        check_regex(&regex)?;
+        analysis.regex = Some(regex);
+    } else {
+        return Err(meta.error("unsupported konfik attribute"));
+    }
+    Ok(())
+}
+
+/// Parses the source of a `precedence` attribute into the name of its `Source` variant
+fn parse_precedence(precedence: &syn::LitStr) -> Result<syn::Ident, syn::Error> {
+    let source = match precedence.value().as_str() {
+        "file" => "File",
+        "remote" => "Remote",
+        "env" => "Env",
+        "credential" => "Credential",
+        "cli" => "Cli",
+        "custom" => "Custom",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                precedence,
+                "expected one of `file`, `remote`, `env`, `credential`, `cli` or `custom`",
+            ));
+        }
+    };
+    Ok(syn::Ident::new(source, precedence.span()))
+}
+
+/// Checks that the pattern of a `regex` attribute compiles
+#[cfg(feature = "regex")]
+fn check_regex(regex: &syn::LitStr) -> Result<(), syn::Error> {
+    regex_syntax::Parser::new()
+        .parse(&regex.value())
+        .map(drop)
+        .map_err(|e| syn::Error::new_spanned(regex, format!("invalid regex: {e}")))
+}
+
+/// Rejects the `regex` attribute, since it can't be validated without the feature
+#[cfg(not(feature = "regex"))]
+fn check_regex(regex: &syn::LitStr) -> Result<(), syn::Error> {
+    Err(syn::Error::new_spanned(
+        regex,
+        "the `regex` attribute requires the `regex` feature of konfik",
+    ))
+}
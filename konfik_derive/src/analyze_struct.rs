@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use syn::{Data, DeriveInput, Type};
+
+/// Analysis result for the struct itself
+pub struct StructAnalysis {
+    pub deny_unknown_fields: bool,
+}
+
+/// Analyze the struct level attributes
+pub fn analyze_struct(input: &DeriveInput) -> Result<StructAnalysis, syn::Error> {
+    reject_borrowed(input)?;
+
+    let mut analysis = StructAnalysis {
+        deny_unknown_fields: false,
+    };
+
+    for attr in &input.attrs {
+        // handle #[konfik(...)]
+        if attr.path().is_ident("konfik") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("deny_unknown_fields") {
+                    analysis.deny_unknown_fields = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported konfik attribute"))
+                }
+            })?;
+        }
+    }
+
+    // Enums have no field metadata, so every key would be unknown
+    if analysis.deny_unknown_fields && matches!(input.data, Data::Enum(_)) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`deny_unknown_fields` isn't supported on enums, use `#[serde(deny_unknown_fields)]` on the variants instead",
+        ));
+    }
+
+    Ok(analysis)
+}
+
+/// Configs are deserialized from a temporary `serde_json::Value`, so they can't borrow from it
+fn reject_borrowed(input: &DeriveInput) -> Result<(), syn::Error> {
+    const HELP: &str = "konfik configs must own their data, since they are deserialized from \
+        a temporary value; use owned types like `String` instead of `&str`";
+
+    if let Some(lifetime) = input.generics.lifetimes().next() {
+        return Err(syn::Error::new_spanned(lifetime, HELP));
+    }
+
+    if let Data::Struct(data) = &input.data
+        && let Some(field) = data
+            .fields
+            .iter()
+            .find(|field| matches!(field.ty, Type::Reference(_)))
+    {
+        return Err(syn::Error::new_spanned(&field.ty, HELP));
+    }
+
+    Ok(())
+}
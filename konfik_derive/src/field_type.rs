@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{GenericArgument, PathArguments, Type, TypePath};
+
+/// Generates the `FieldType` expression describing `ty`
+pub fn field_type_tokens(ty: &Type, nested: bool) -> TokenStream2 {
+    if nested {
+        return quote! { ::konfik::config_meta::FieldType::Nested };
+    }
+
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return quote! { ::konfik::config_meta::FieldType::Unknown };
+    };
+    let Some(segment) = path.segments.last() else {
+        return quote! { ::konfik::config_meta::FieldType::Unknown };
+    };
+
+    let ident = segment.ident.to_string();
+    let inner = first_type_argument(&segment.arguments);
+
+    match (ident.as_str(), inner) {
+        ("Option", Some(inner)) => {
+            let inner = field_type_tokens(inner, false);
+            quote! { ::konfik::config_meta::FieldType::Option(Box::new(#inner)) }
+        }
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", Some(inner)) => {
+            let inner = field_type_tokens(inner, false);
+            quote! { ::konfik::config_meta::FieldType::Vec(Box::new(#inner)) }
+        }
+        ("HashMap" | "BTreeMap", _) => quote! { ::konfik::config_meta::FieldType::Map },
+        _ => quote! { ::konfik::config_meta::FieldType::Scalar(#ident) },
+    }
+}
+
+/// Returns the first generic type argument, e.g. `T` of `Option<T>`
+fn first_type_argument(arguments: &PathArguments) -> Option<&Type> {
+    let PathArguments::AngleBracketed(arguments) = arguments else {
+        return None;
+    };
+
+    arguments.args.iter().find_map(|argument| match argument {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
@@ -1,49 +1,98 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 kingananas20
 
-use crate::analyze_field::{FieldAnalysis, analyze_field};
+use crate::{
+    analyze_field::{FieldAnalysis, analyze_field},
+    analyze_struct::StructAnalysis,
+    field_type::field_type_tokens,
+};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{Fields, Ident, LitStr, Type, TypePath};
+use syn::{Fields, Generics, Ident, LitStr};
 
 #[expect(clippy::unwrap_used)]
-pub fn generate_config_meta(fields: &Fields, parent_name: &Ident) -> TokenStream2 {
+pub fn generate_config_meta(
+    fields: &Fields,
+    parent_name: &Ident,
+    generics: &Generics,
+    struct_analysis: &StructAnalysis,
+) -> Result<TokenStream2, syn::Error> {
     let mut field_meta_tokens = Vec::new();
     let mut field_impl_tokens = Vec::new();
+    let mut carry_skipped_tokens = Vec::new();
 
     for field in fields {
-        let fname = field.ident.as_ref().unwrap().to_string();
+        let ident = field.ident.as_ref().unwrap();
+        let fname = ident.to_string();
         let fname_lit = LitStr::new(&fname, Span::call_site());
 
-        let ty_str = match &field.ty {
-            Type::Path(TypePath { path, .. }) => path.segments.last().unwrap().ident.to_string(),
-            _ => "unknown".to_string(),
-        };
-        let ty_lit = LitStr::new(&ty_str, Span::call_site());
-
         let FieldAnalysis {
             skip,
             required,
             has_default,
             nested,
-        } = analyze_field(field).unwrap();
-
-        field_meta_tokens.push(quote! { ::konfik::config_meta::FieldMeta {
-            name: #fname_lit,
-            path: #fname_lit.to_string(),
-            ty: #ty_lit,
-            required: #required,
-            skip: #skip,
-            has_default: #has_default,
-            nested: #nested
+            positional,
+            subcommand,
+            cli_skip,
+            size,
+            deprecated_aliases,
+            default_if_none,
+            null_uses_default,
+            env,
+            cli,
+            transform,
+            precedence,
+            regex,
+            default_fn,
+            eval_default,
+        } = analyze_field(field)?;
+
+        let cli = cli.map_or_else(|| quote! { None }, |cli| quote! { Some(#cli) });
+        let default_if_none =
+            default_if_none.map_or_else(|| quote! { None }, |default| quote! { Some(#default) });
+        let default_fn = default_fn_tokens(default_fn, eval_default);
+        let regex = regex.map_or_else(|| quote! { None }, |regex| quote! { Some(#regex) });
+        let precedence = precedence.map_or_else(
+            || quote! { None },
+            |source| quote! { Some(::konfik::Source::#source) },
+        );
+        let transform = transform.map_or_else(
+            || quote! { None },
+            |transform| quote! { Some(#transform as ::konfik::config_meta::Transform) },
+        );
+
+        let ty_tokens = field_type_tokens(&field.ty, nested);
+
+        field_meta_tokens.push(quote! {{
+            let mut field = ::konfik::config_meta::FieldMeta::new(#fname_lit, #ty_tokens);
+            field.required = #required;
+            field.skip = #skip;
+            field.has_default = #has_default;
+            field.nested = #nested;
+            field.positional = #positional;
+            field.subcommand = #subcommand;
+            field.cli_skip = #cli_skip;
+            field.size = #size;
+            field.deprecated_aliases = &[#(#deprecated_aliases),*];
+            field.default_if_none = #default_if_none;
+            field.null_uses_default = #null_uses_default;
+            field.env = &[#(#env),*];
+            field.cli = #cli;
+            field.transform = #transform;
+            field.precedence = #precedence;
+            field.regex = #regex;
+            field.default_fn = #default_fn;
+            field
         }});
 
+        let ty = field.ty.clone();
+
+        carry_skipped_tokens.extend(carry_skipped(ident, &ty, skip, nested));
+
         if !nested {
             continue;
         }
 
-        let ty = field.ty.clone();
-
         field_impl_tokens.push(quote! {
             {
                 fields.extend(Self::correct_paths(<#ty as ::konfik::config_meta::ConfigMeta>::config_metadata(), #fname));
@@ -51,16 +100,73 @@ pub fn generate_config_meta(fields: &Fields, parent_name: &Ident) -> TokenStream
         });
     }
 
-    quote! {
-        impl ::konfik::config_meta::ConfigMeta for #parent_name {
+    let deny_unknown_fields = struct_analysis.deny_unknown_fields;
+    let as_any = as_any_tokens(generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::konfik::config_meta::ConfigMeta for #parent_name #ty_generics #where_clause {
             fn config_metadata() -> Vec<::konfik::config_meta::FieldMeta> {
-                let mut fields = vec![ #(#field_meta_tokens),* ];
+                let mut fields: Vec<::konfik::config_meta::FieldMeta> = vec![ #(#field_meta_tokens),* ];
 
                 #(#field_impl_tokens)*
 
                 fields.retain(|field| !field.nested);
                 fields
             }
+
+            fn deny_unknown_fields() -> bool {
+                #deny_unknown_fields
+            }
+
+            fn carry_skipped(&mut self, previous: &mut Self) {
+                #(#carry_skipped_tokens)*
+                let _ = previous;
+            }
+
+            #as_any
+        }
+    })
+}
+
+/// `ConfigMeta::as_any`, only types without generic parameters are known to be `'static`
+fn as_any_tokens(generics: &Generics) -> Option<TokenStream2> {
+    generics.params.is_empty().then(|| {
+        quote! {
+            const HAS_ANY: bool = true;
+
+            fn as_any(&self) -> Option<&dyn ::core::any::Any> {
+                Some(self)
+            }
         }
+    })
+}
+
+/// The `DefaultFn` of a field, only evaluated on request since the default's type doesn't
+/// have to implement `Serialize` otherwise
+fn default_fn_tokens(default_fn: Option<syn::Path>, eval_default: bool) -> TokenStream2 {
+    default_fn.filter(|_| eval_default).map_or_else(
+        || quote! { None },
+        |default_fn| {
+            quote! {
+                Some((|| ::konfik::config_meta::serialize_default(#default_fn()))
+                    as ::konfik::config_meta::DefaultFn)
+            }
+        },
+    )
+}
+
+/// Moves the value of a skipped field, or the skipped fields of a nested one, from `previous`
+fn carry_skipped(ident: &Ident, ty: &syn::Type, skip: bool, nested: bool) -> Option<TokenStream2> {
+    if skip {
+        Some(quote! {
+            ::core::mem::swap(&mut self.#ident, &mut previous.#ident);
+        })
+    } else if nested {
+        Some(quote! {
+            <#ty as ::konfik::config_meta::ConfigMeta>::carry_skipped(&mut self.#ident, &mut previous.#ident);
+        })
+    } else {
+        None
     }
 }
@@ -5,43 +5,69 @@
 //!
 //! Procedural macro derive for the [`konfik`](https://docs.rs/konfik) configuration parsing library.
 //!
-//! This crate provides the `#[derive(Config)]` macro that automatically implements the necessary
-//! traits for structs to work seamlessly with the `konfik` configuration loader.
+//! This crate provides the `#[derive(Konfik)]` and `#[derive(Nested)]` macros that automatically
+//! implement the necessary traits for structs to work seamlessly with the `konfik` configuration
+//! loader. `Konfik` implements `ConfigMeta` and `LoadConfig` for top level configs, `Nested`
+//! only implements `ConfigMeta` for structs used in `#[konfik(nested)]` fields.
+//!
+//! Use the macros through their re-exports, `konfik::Konfik` and `konfik::Nested`.
 
 mod analyze_field;
+mod analyze_struct;
+mod field_type;
 mod generate_config_meta;
 
+use analyze_struct::analyze_struct;
 use generate_config_meta::generate_config_meta;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{Data, DeriveInput, parse_macro_input};
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
 
 /// # `Konfik`
 ///
 /// Implements `ConfigMeta` and `LoadConfig` for itself.
 ///
+/// Enums are supported, but have no field metadata: they are only loaded from files.
+///
 /// # Panics
 /// Panics when appliead to structs without named fields and
-/// on non struct types.
-#[proc_macro_derive(Konfik, attributes(konfik, serde, command))]
+/// on unions.
+#[proc_macro_derive(Konfik, attributes(konfik, serde, command, arg))]
 pub fn derive_config(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
-    let Data::Struct(data) = &input.data else {
-        return syn::Error::new_spanned(&input, "Only structs are supported")
-            .to_compile_error()
-            .into();
+    let fields = match config_fields(&input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let analysis = match analyze_struct(&input) {
+        Ok(analysis) => analysis,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let config_meta = match generate_config_meta(&fields, name, &input.generics, &analysis) {
+        Ok(config_meta) => config_meta,
+        Err(e) => return e.to_compile_error().into(),
     };
 
-    let config_meta = generate_config_meta(&data.fields, name);
+    // Generic configs only load if their type parameters make them deserializable
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(Self: ::serde::de::DeserializeOwned + 'static));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     TokenStream::from(quote! {
         #config_meta
 
-        impl ::konfik::LoadConfig for #name {
+        impl #impl_generics ::konfik::LoadConfig for #name #ty_generics #where_clause {
             fn load() -> Result<Self, ::konfik::Error> {
-                ::konfik::ConfigLoader::default().load()
+                ::konfik::default_loader().load()
             }
         }
     })
@@ -50,20 +76,43 @@ pub fn derive_config(input: TokenStream) -> TokenStream {
 /// # `Nested`
 ///
 /// Implements `ConfigMeta` for itself.
-#[proc_macro_derive(Nested, attributes(konfik, serde, command))]
+#[proc_macro_derive(Nested, attributes(konfik, serde, command, arg))]
 pub fn derive_nested_types(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
 
-    let Data::Struct(data) = &input.data else {
-        return syn::Error::new_spanned(&input, "Only structs are supported")
-            .to_compile_error()
-            .into();
+    let fields = match config_fields(&input) {
+        Ok(fields) => fields,
+        Err(e) => return e.to_compile_error().into(),
     };
 
-    let config_meta = generate_config_meta(&data.fields, name);
+    let analysis = match analyze_struct(&input) {
+        Ok(analysis) => analysis,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let config_meta = match generate_config_meta(&fields, name, &input.generics, &analysis) {
+        Ok(config_meta) => config_meta,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     TokenStream::from(quote! {
         #config_meta
     })
 }
+
+/// The fields metadata is generated for
+///
+/// Enums, e.g. serde tagged unions of different backends, have no fields: which fields
+/// exist and are required depends on the variant, so they are loaded from files and
+/// checked by serde, but not from env vars or CLI args.
+fn config_fields(input: &DeriveInput) -> Result<Fields, syn::Error> {
+    match &input.data {
+        Data::Struct(data) => Ok(data.fields.clone()),
+        Data::Enum(_) => Ok(Fields::Unit),
+        Data::Union(_) => Err(syn::Error::new_spanned(
+            input,
+            "Only structs and enums are supported",
+        )),
+    }
+}
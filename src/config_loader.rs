@@ -1,38 +1,260 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 kingananas20
 
+mod doctor;
+mod drift;
+mod dump;
 mod load;
 mod load_cli;
+mod load_credentials;
+mod load_dir;
 mod load_env;
 mod load_file;
-mod parse_env;
+mod load_location;
+mod migrate;
+mod normalize;
+#[cfg(feature = "regex")]
+mod patterns;
+mod print_config;
+#[cfg(feature = "prompt")]
+mod prompt;
+mod provenance;
+mod redact;
+mod references;
+mod secrets;
+mod subscribe;
+mod template;
+#[cfg(feature = "watch")]
+mod watch;
 
-pub use load_file::ParseFileFormatError;
+pub use load_file::{FileFormat, ParseFileFormatError};
+pub use subscribe::Subscription;
 
-use crate::Error;
+use crate::{
+    ArrayMerge, Error, ImmutableMode, Priority, Source, ValidationMode, config_meta::ConfigMeta,
+    config_source::ConfigSource,
+};
 use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    ffi::OsString,
     fmt::Debug,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 
+/// Validation function run against the merged configuration
+type Validator = dyn Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync;
+
+/// Validation function run against a single config file before it is merged: `(path, value)`
+type FileValidator = dyn Fn(&Path, &serde_json::Value) -> Result<(), Error> + Send + Sync;
+
+/// Migration of a config from one version to the next
+type Migration = dyn Fn(serde_json::Value) -> Result<serde_json::Value, Error> + Send + Sync;
+
+/// Handler called when a source overrides a different value: `(path, old, new, source)`
+type ConflictHandler = dyn Fn(&str, &serde_json::Value, &serde_json::Value, Source) + Send + Sync;
+
+/// Handler called with non fatal problems, like deprecated keys
+type WarningHandler = dyn Fn(&str) + Send + Sync;
+
+/// Resolver looking up the value of a `secret://<name>` reference by name
+type SecretResolver = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Customization of the clap command built for the CLI args
+type CommandCustomizer = dyn Fn(clap::Command) -> clap::Command + Send + Sync;
+
+/// Hook run with the deserialized configuration, only called with its own type
+type PostLoadHook = dyn Fn(&dyn Any) -> Result<(), Error> + Send + Sync;
+
 /// Configuration loader with clean, composable API
+///
+/// A configured loader is `Send + Sync + Clone`, so it can be built once, stored
+/// in the application state and used to load configs again from any thread:
+///
+/// ```rust
+/// use konfik::{ConfigLoader, Error, Konfik};
+/// use std::{sync::Arc, thread};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     #[serde(default)]
+///     port: u16,
+/// }
+///
+/// let loader = Arc::new(ConfigLoader::default().with_validation(|_| Ok(())));
+///
+/// let handles = (0..2)
+///     .map(|_| {
+///         let loader = Arc::clone(&loader);
+///         thread::spawn(move || loader.load::<AppConfig>().map(|config| config.port))
+///     })
+///     .collect::<Vec<_>>();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().expect("thread panicked")?, 0);
+/// }
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct ConfigLoader {
     env_prefix: Option<String>,
-    config_files: Vec<PathBuf>,
-    #[expect(clippy::type_complexity)]
-    validation: Option<Box<dyn Fn(&serde_json::Value) -> Result<(), Error>>>,
+    config_files: Vec<(PathBuf, Priority, i32)>,
+    file_formats: HashMap<PathBuf, FileFormat>,
+    layered_dirs: Vec<PathBuf>,
+    config_dir_env_vars: Vec<String>,
+    first_match_files: Vec<Vec<PathBuf>>,
+    ancestor_files: Vec<(PathBuf, bool)>,
+    cargo_metadata: Option<String>,
+    config_file_env_vars: Vec<String>,
+    validation: Option<Arc<Validator>>,
+    source_validation: Vec<(Source, Arc<Validator>)>,
+    mode_validation: Vec<(ValidationMode, Arc<Validator>)>,
+    file_validation: Vec<Arc<FileValidator>>,
+    env_validation: Vec<(String, Arc<Validator>)>,
+    environment_var: Option<String>,
+    migrations: Vec<(u64, Arc<Migration>)>,
+    cli_args: Option<Vec<OsString>>,
+    ignore_unknown_args: bool,
+    command_customizers: Vec<Arc<CommandCustomizer>>,
+    print_config_flag: bool,
+    post_load: Vec<(TypeId, Arc<PostLoadHook>)>,
+    immutable_fields: Vec<String>,
+    immutable_mode: ImmutableMode,
+    deny_unknown_fields: bool,
+    config_location: Option<(String, String)>,
+    remote_urls: Vec<String>,
+    remote_timeout: Option<Duration>,
+    remote_headers: Vec<(String, String)>,
+    conflict_handler: Option<Arc<ConflictHandler>>,
+    warning_handler: Option<Arc<WarningHandler>>,
+    subcommand_tag: String,
+    env_trim: bool,
+    env_allowlist: Option<Vec<String>>,
+    env_vars: Option<HashMap<String, OsString>>,
+    env_override: Option<(String, char)>,
+    env_globs: Vec<(String, String)>,
+    sources: Vec<(Arc<dyn ConfigSource + Send + Sync>, Priority)>,
+    redacted_paths: Vec<String>,
+    value_references: bool,
+    secret_resolver: Option<Arc<SecretResolver>>,
+    delete_sentinel: Option<serde_json::Value>,
+    array_merge: ArrayMerge,
+    coerce_scalar_strings: bool,
+    bool_literals: Option<(Vec<String>, Vec<String>)>,
+    case_insensitive_keys: bool,
+    float_precision: Option<usize>,
+    systemd_credentials: Option<HashMap<String, String>>,
+    namespace: Option<String>,
+    namespace_required: bool,
+    profiles: Vec<String>,
+    fill_defaults: Option<serde_json::Value>,
+    require_explicit: bool,
+    #[cfg(feature = "prompt")]
+    interactive_prompts: bool,
 }
 
 impl Debug for ConfigLoader {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ConfigLoader")
+        let mut debug = f.debug_struct("ConfigLoader");
+        debug
             .field("env_prefix", &self.env_prefix)
             .field("config_files", &self.config_files)
+            .field("file_formats", &self.file_formats)
+            .field("layered_dirs", &self.layered_dirs)
+            .field("config_dir_env_vars", &self.config_dir_env_vars)
+            .field("first_match_files", &self.first_match_files)
+            .field("ancestor_files", &self.ancestor_files)
+            .field("cargo_metadata", &self.cargo_metadata)
+            .field("config_file_env_vars", &self.config_file_env_vars)
             .field(
                 "validation",
-                &"Option<Box<dyn Fn(&serde_json::Value) -> Result<(), Error>>>",
+                &"Option<Arc<dyn Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync>>",
             )
-            .finish()
+            .field(
+                "source_validation",
+                &self
+                    .source_validation
+                    .iter()
+                    .map(|(source, _)| source)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "mode_validation",
+                &self
+                    .mode_validation
+                    .iter()
+                    .map(|(mode, _)| mode)
+                    .collect::<Vec<_>>(),
+            )
+            .field("file_validation", &self.file_validation.len())
+            .field(
+                "env_validation",
+                &self
+                    .env_validation
+                    .iter()
+                    .map(|(environment, _)| environment)
+                    .collect::<Vec<_>>(),
+            )
+            .field("environment_var", &self.environment_var)
+            .field(
+                "migrations",
+                &self
+                    .migrations
+                    .iter()
+                    .map(|(from, _)| from)
+                    .collect::<Vec<_>>(),
+            )
+            .field("cli_args", &self.cli_args)
+            .field("ignore_unknown_args", &self.ignore_unknown_args)
+            .field("command_customizers", &self.command_customizers.len())
+            .field("print_config_flag", &self.print_config_flag)
+            .field("post_load", &self.post_load.len())
+            .field("immutable_fields", &self.immutable_fields)
+            .field("immutable_mode", &self.immutable_mode)
+            .field("deny_unknown_fields", &self.deny_unknown_fields)
+            .field("config_location", &self.config_location)
+            .field("remote_urls", &self.remote_urls)
+            .field("remote_timeout", &self.remote_timeout)
+            .field(
+                "remote_headers",
+                &self
+                    .remote_headers
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("conflict_handler", &self.conflict_handler.is_some())
+            .field("warning_handler", &self.warning_handler.is_some())
+            .field("subcommand_tag", &self.subcommand_tag)
+            .field("env_trim", &self.env_trim)
+            .field("env_allowlist", &self.env_allowlist)
+            .field("env_vars", &self.env_vars)
+            .field("env_override", &self.env_override)
+            .field("env_globs", &self.env_globs)
+            .field("sources", &self.sources.len())
+            .field("redacted_paths", &self.redacted_paths)
+            .field("value_references", &self.value_references)
+            .field("secret_resolver", &self.secret_resolver.is_some())
+            .field("delete_sentinel", &self.delete_sentinel)
+            .field("array_merge", &self.array_merge)
+            .field("coerce_scalar_strings", &self.coerce_scalar_strings)
+            .field("bool_literals", &self.bool_literals)
+            .field("case_insensitive_keys", &self.case_insensitive_keys)
+            .field("float_precision", &self.float_precision)
+            .field("systemd_credentials", &self.systemd_credentials)
+            .field("namespace", &self.namespace)
+            .field("namespace_required", &self.namespace_required)
+            .field("profiles", &self.profiles)
+            .field("fill_defaults", &self.fill_defaults)
+            .field("require_explicit", &self.require_explicit);
+
+        #[cfg(feature = "prompt")]
+        debug.field("interactive_prompts", &self.interactive_prompts);
+
+        debug.finish()
     }
 }
 
@@ -41,11 +263,62 @@ impl Default for ConfigLoader {
         Self {
             env_prefix: Some(String::new()),
             config_files: vec![
-                "config.json".into(),
-                "config.yaml".into(),
-                "config.toml".into(),
+                ("config.json".into(), Priority::Normal, 0),
+                ("config.yaml".into(), Priority::Normal, 0),
+                ("config.toml".into(), Priority::Normal, 0),
             ],
+            file_formats: HashMap::new(),
+            layered_dirs: Vec::new(),
+            config_dir_env_vars: Vec::new(),
+            first_match_files: Vec::new(),
+            ancestor_files: Vec::new(),
+            cargo_metadata: None,
+            config_file_env_vars: Vec::new(),
             validation: None,
+            source_validation: Vec::new(),
+            mode_validation: Vec::new(),
+            file_validation: Vec::new(),
+            env_validation: Vec::new(),
+            environment_var: None,
+            migrations: Vec::new(),
+            cli_args: None,
+            ignore_unknown_args: false,
+            command_customizers: Vec::new(),
+            print_config_flag: false,
+            post_load: Vec::new(),
+            immutable_fields: Vec::new(),
+            immutable_mode: ImmutableMode::Reject,
+            deny_unknown_fields: false,
+            config_location: None,
+            remote_urls: Vec::new(),
+            remote_timeout: None,
+            remote_headers: Vec::new(),
+            conflict_handler: None,
+            warning_handler: None,
+            subcommand_tag: "_subcommand".to_string(),
+            env_trim: true,
+            env_allowlist: None,
+            env_vars: None,
+            env_override: None,
+            env_globs: Vec::new(),
+            sources: Vec::new(),
+            redacted_paths: Vec::new(),
+            value_references: false,
+            secret_resolver: None,
+            delete_sentinel: None,
+            array_merge: ArrayMerge::Replace,
+            coerce_scalar_strings: false,
+            bool_literals: None,
+            case_insensitive_keys: false,
+            float_precision: None,
+            systemd_credentials: None,
+            namespace: None,
+            namespace_required: false,
+            profiles: Vec::new(),
+            fill_defaults: None,
+            require_explicit: false,
+            #[cfg(feature = "prompt")]
+            interactive_prompts: false,
         }
     }
 }
@@ -59,17 +332,1801 @@ impl ConfigLoader {
     }
 
     /// Add a config file to check (in order)
+    ///
+    /// The path must point to a file, loading fails if it is a directory. Use
+    /// [`with_layered_dirs`](Self::with_layered_dirs) to load config directories.
+    /// Missing files are skipped, but files the process isn't allowed to read fail
+    /// the load with an [`Error::PermissionDenied`].
+    ///
+    /// ```rust
+    /// # use konfik::{ConfigLoader, Konfik};
+    /// # #[derive(serde::Deserialize, Konfik)]
+    /// # struct AppConfig { port: Option<u16> }
+    /// let dir = std::env::temp_dir();
+    /// let err = ConfigLoader::default()
+    ///     .with_config_file(&dir)
+    ///     .load::<AppConfig>()
+    ///     .err()
+    ///     .map(|e| e.to_string());
+    ///
+    /// assert!(err.is_some_and(|e| e.contains("expected a file, got directory")));
+    /// ```
+    ///
+    /// A file that is listed more than once, even under different paths like
+    /// `./config.toml` and `config.toml`, is only loaded at its last position:
+    ///
+    /// ```rust
+    /// # use konfik::{ConfigLoader, Konfik};
+    /// # use std::sync::{Arc, Mutex};
+    /// # #[derive(serde::Deserialize, Konfik)]
+    /// # struct AppConfig { port: u16 }
+    /// let dir = std::env::temp_dir();
+    /// let base = dir.join("konfik_dedup_base.toml");
+    /// let other = dir.join("konfik_dedup_other.toml");
+    /// std::fs::write(&base, "port = 80")?;
+    /// std::fs::write(&other, "port = 8080")?;
+    ///
+    /// let conflicts = Arc::new(Mutex::new(0));
+    /// let count = Arc::clone(&conflicts);
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&base)
+    ///     .with_config_file(&other)
+    ///     .with_config_file(dir.join(".").join("konfik_dedup_base.toml"))
+    ///     .with_conflict_handler(move |_, _, _, _| *count.lock().expect("poisoned") += 1)
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 80);
+    /// assert_eq!(*conflicts.lock().expect("poisoned"), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
     #[must_use]
-    pub fn with_config_file<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.config_files.push(path.as_ref().to_path_buf());
+    pub fn with_config_file<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_config_file_prio(path, 0)
+    }
+
+    /// Add a config file that is parsed as `format`, whatever its extension is
+    ///
+    /// Useful for files like `app.conf` or fragments without an extension, which would
+    /// otherwise be parsed as JSON. Like other config files, it is skipped if it doesn't exist.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, FileFormat, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     name: String,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir();
+    /// let conf = dir.join("konfik_file_as.conf");
+    /// let json = dir.join("konfik_file_as.json");
+    /// std::fs::write(&conf, "port = 8080")?;
+    /// std::fs::write(&json, "name: konfik")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file_as(&conf, FileFormat::Toml)
+    ///     .with_config_file_as(&json, FileFormat::Yaml)
+    ///     .with_config_file_as(dir.join("konfik_file_as_missing"), FileFormat::Toml)
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(config.name, "konfik");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_file_as<P: AsRef<Path>>(mut self, path: P, format: FileFormat) -> Self {
+        self = self.with_config_file(&path);
+        self.file_formats
+            .insert(path.as_ref().to_path_buf(), format);
         self
     }
 
     /// Clear default config files and set specific ones
     #[must_use]
     pub fn with_config_files<P: AsRef<Path>>(mut self, files: Vec<P>) -> Self {
+        self.config_files.extend(
+            files
+                .iter()
+                .map(|p| (p.as_ref().to_path_buf(), Priority::Normal, 0)),
+        );
+        self
+    }
+
+    /// Add config files with an explicit [`Priority`]
+    ///
+    /// Formats can be mixed freely, the format of every file is picked by its extension.
+    /// [`Priority::Low`] layers are merged first, [`Priority::Normal`] layers are regular
+    /// config files and [`Priority::High`] layers are merged after all other config files,
+    /// remote configs and [`with_source`](Self::with_source) sources. Only the file of
+    /// [`with_config_file_from_env`](Self::with_config_file_from_env), high priority custom
+    /// sources, env vars and CLI args override them. See [`Priority`] for the full order.
+    /// Missing files are skipped.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Priority};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     name: String,
+    ///     port: u16,
+    ///     workers: u8,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir();
+    /// let generated = dir.join("konfik_layers_generated.json");
+    /// let base = dir.join("konfik_layers_base.yaml");
+    /// let local = dir.join("konfik_layers_local.toml");
+    /// std::fs::write(&generated, r#"{ "port": 9000 }"#)?;
+    /// std::fs::write(&base, "name: app\nport: 80\nworkers: 2")?;
+    /// std::fs::write(&local, "port = 8080\nworkers = 4")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_layers(vec![
+    ///         (generated, Priority::High),
+    ///         (base, Priority::Low),
+    ///         (local, Priority::Normal),
+    ///     ])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.name, "app");
+    /// assert_eq!(config.port, 9000);
+    /// assert_eq!(config.workers, 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_layers<P: AsRef<Path>>(mut self, layers: Vec<(P, Priority)>) -> Self {
+        self.config_files.extend(
+            layers
+                .iter()
+                .map(|(path, priority)| (path.as_ref().to_path_buf(), *priority, 0)),
+        );
+        self
+    }
+
+    /// Add a config file with an explicit numeric priority
+    ///
+    /// All config files of [`Priority::Normal`] are sorted by this number before merging:
+    /// lower numbers are merged first, so higher numbers override them. Every other file
+    /// has the priority `0`, e.g. a file with priority `-10` is overridden by the regular
+    /// config files. Files with the same priority are merged in the order they were
+    /// added. Unlike the insertion order of [`with_config_file`](Self::with_config_file),
+    /// the precedence of many fragments is stated where each of them is added.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     name: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir();
+    /// let site = dir.join("konfik_prio_site.toml");
+    /// let base = dir.join("konfik_prio_base.toml");
+    /// std::fs::write(&site, "port = 8080")?;
+    /// std::fs::write(&base, "name = \"app\"\nport = 80")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file_prio(&site, 20)
+    ///     .with_config_file_prio(&base, 10)
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.name, "app");
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_file_prio<P: AsRef<Path>>(mut self, path: P, prio: i32) -> Self {
         self.config_files
-            .extend(files.iter().map(|p| p.as_ref().to_path_buf()));
+            .push((path.as_ref().to_path_buf(), Priority::Normal, prio));
+        self
+    }
+
+    /// Load the `[package.metadata.<tool>]` table of the nearest `Cargo.toml`
+    ///
+    /// Like cargo subcommands do, developer tools can keep their config in the project
+    /// manifest instead of a separate file. The manifest is searched in the current
+    /// directory and its parents, up to the repository root. The table is merged after the
+    /// [`Priority::Normal`] config files and remote URLs, before the
+    /// [config location](Self::with_config_location), custom sources and
+    /// [`Priority::High`] layers. Like a config file, it gets the namespace, profiles, key
+    /// case and per file validators of the loader. A missing manifest or table is skipped.
+    ///
+    /// ```rust,no_run
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// // Cargo.toml:
+    /// // [package.metadata.mytool]
+    /// // max_line_length = 100
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct ToolConfig {
+    ///     max_line_length: u32,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_cargo_metadata("mytool")
+    ///     .load::<ToolConfig>()?;
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_cargo_metadata(mut self, tool: impl Into<String>) -> Self {
+        self.cargo_metadata = Some(tool.into());
+        self
+    }
+
+    /// Use only the first existing file of `files`
+    ///
+    /// Unlike [`with_config_files`](Self::with_config_files), which merges every existing
+    /// file, only the first file in the list that exists is loaded and the rest are
+    /// ignored. It is merged after the regular config files.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir();
+    /// let user = dir.join("konfik_firstmatch_user.toml");
+    /// let system = dir.join("konfik_firstmatch_system.toml");
+    /// std::fs::write(&user, "port = 8080")?;
+    /// std::fs::write(&system, "port = 80")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file_firstmatch(vec![dir.join("konfik_firstmatch_missing.toml"), user, system])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_file_firstmatch<P: AsRef<Path>>(mut self, files: Vec<P>) -> Self {
+        self.first_match_files
+            .push(files.iter().map(|p| p.as_ref().to_path_buf()).collect());
+        self
+    }
+
+    /// Search for `filename` in the current directory and all of its parents
+    ///
+    /// The search stops at the filesystem root or at the first directory containing
+    /// `.git`, which is still searched. Every file found is merged, files closer to the
+    /// current directory take priority. Missing files are skipped. The files are merged
+    /// after the first match files.
+    ///
+    /// ```rust,no_run
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     name: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// // Run from `project/crates/app`: `project/crates/app/konfik.toml` overrides
+    /// // `project/konfik.toml`
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file_ancestors("konfik.toml")
+    ///     .load::<AppConfig>()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_file_ancestors(mut self, filename: impl AsRef<Path>) -> Self {
+        self.ancestor_files
+            .push((filename.as_ref().to_path_buf(), false));
+        self
+    }
+
+    /// Like [`with_config_file_ancestors`](Self::with_config_file_ancestors), but only
+    /// the file closest to the current directory is loaded
+    #[must_use]
+    pub fn with_config_file_nearest_ancestor(mut self, filename: impl AsRef<Path>) -> Self {
+        self.ancestor_files
+            .push((filename.as_ref().to_path_buf(), true));
+        self
+    }
+
+    /// Add the config file named by the env var `env_var`, read at load time
+    ///
+    /// The file is merged after every other config file, including [`Priority::High`]
+    /// layers, remote configs and [`Priority::Normal`] custom sources, so it has the
+    /// highest file priority. Only [`Priority::High`] custom sources, env vars and CLI args
+    /// override it. Nothing happens if the variable is unset. The variable name is used as
+    /// is, without the env prefix.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_config_file_from_env.toml");
+    /// std::fs::write(&path, "port = 8080")?;
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("KONFIK_DOC_CONFIG_FILE", &path) };
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file_from_env("KONFIK_DOC_CONFIG_FILE")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_file_from_env(mut self, env_var: impl Into<String>) -> Self {
+        self.config_file_env_vars.push(env_var.into());
+        self
+    }
+
+    /// Parse these arguments instead of [`std::env::args_os`] when loading CLI args
+    ///
+    /// The first argument is the binary name, just like with `clap`'s `get_matches_from`.
+    ///
+    /// Fields marked `#[konfik(positional)]` (or `#[arg(index = N)]`) stay positional:
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct AppConfig {
+    ///     #[konfik(positional)]
+    ///     database_url: String,
+    ///     #[arg(long)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_cli_args(["myapp", "postgres://localhost", "--port", "5432"])
+    ///     .load_with_cli::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.database_url, "postgres://localhost");
+    /// assert_eq!(config.port, 5432);
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_cli_args<I, A>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<OsString>,
+    {
+        self.cli_args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Skip CLI args the config type doesn't know instead of exiting with a usage error
+    ///
+    /// Use it when other parts of the binary consume their own flags. Unknown flags are
+    /// dropped before `clap` parses the args, together with the arg following them unless
+    /// it starts with `-` or the value is attached, e.g. `--tracing-level=debug`. The known
+    /// args are still checked, invalid values and missing required flags exit with a usage
+    /// error like without this option.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct AppConfig {
+    ///     #[arg(long)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_cli_args(["myapp", "--port", "8080", "--tracing-level", "debug"])
+    ///     .with_ignore_unknown_args(true)
+    ///     .load_with_cli::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_ignore_unknown_args(mut self, ignore: bool) -> Self {
+        self.ignore_unknown_args = ignore;
+        self
+    }
+
+    /// Customize the clap command before the CLI args are matched
+    ///
+    /// `f` gets the command after konfik adjusted it: args of fields still missing from
+    /// other sources are required and every field has a long flag, so `f` sees and can
+    /// change those. Use it to add global flags, styles or help texts. Values of args
+    /// added by `f` aren't merged into the config, read them from the matches returned
+    /// by [`load_with_matches`](Self::load_with_matches). Customizers run in the order
+    /// they were added.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct AppConfig {
+    ///     #[arg(long)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let (config, matches) = ConfigLoader::default()
+    ///     .with_cli_args(["myapp", "--port", "8080", "--verbose"])
+    ///     .with_command_customizer(|cmd| {
+    ///         cmd.arg(clap::Arg::new("verbose").long("verbose").action(clap::ArgAction::SetTrue))
+    ///     })
+    ///     .load_with_matches::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert!(matches.get_flag("verbose"));
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_command_customizer<F>(mut self, f: F) -> Self
+    where
+        F: Fn(clap::Command) -> clap::Command + Send + Sync + 'static,
+    {
+        self.command_customizers.push(Arc::new(f));
+        self
+    }
+
+    /// Add `--print-config` and `--print-config-format=json|yaml|toml` to the CLI
+    ///
+    /// With `--print-config`, [`load_with_cli`](Self::load_with_cli) prints the merged
+    /// config, TOML unless another format is chosen, and exits the process with status 0.
+    /// Field precedences, migrations and defaults apply like when loading, and values at
+    /// [redacted paths](Self::with_redacted_paths) and `secret://` references are replaced. Required fields
+    /// aren't enforced on the CLI then, fields that no source sets are printed as `"<missing>"`.
+    ///
+    /// ```rust,no_run
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct AppConfig {
+    ///     #[arg(long)]
+    ///     port: u16,
+    /// }
+    ///
+    /// // `myapp --print-config --print-config-format json` prints `{ "port": "<missing>" }`
+    /// let config = ConfigLoader::default()
+    ///     .with_print_config_flag(true)
+    ///     .load_with_cli::<AppConfig>()?;
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_print_config_flag(mut self, enabled: bool) -> Self {
+        self.print_config_flag = enabled;
+        self
+    }
+
+    /// Add layered config directories, in order of increasing precedence
+    ///
+    /// Each directory contributes its `config.json`, `config.yaml` and `config.toml`
+    /// followed by all supported files in its `conf.d` directory (sorted by name).
+    /// Later directories override earlier ones, missing directories are skipped.
+    /// All layered directories are merged before the files added with
+    /// [`Self::with_config_file`], so those take precedence.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::fs;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let root = std::env::temp_dir().join("konfik_layered_dirs");
+    /// let (system, user) = (root.join("etc"), root.join("home"));
+    /// fs::create_dir_all(system.join("conf.d"))?;
+    /// fs::create_dir_all(&user)?;
+    /// fs::write(system.join("config.toml"), "host = \"localhost\"\nport = 80")?;
+    /// fs::write(system.join("conf.d/10-port.toml"), "port = 8080")?;
+    /// fs::write(user.join("config.json"), r#"{ "host": "example.com" }"#)?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_layered_dirs(&[&system, &user, &root.join("missing")])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.host, "example.com");
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_layered_dirs<P: AsRef<Path>>(mut self, dirs: &[P]) -> Self {
+        self.layered_dirs
+            .extend(dirs.iter().map(|dir| dir.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Add a layered config directory named by the env var `env_var`, e.g. `CONFIG_DIR`
+    ///
+    /// The directory is read like the [layered directories](Self::with_layered_dirs), after
+    /// them, when loading. This fits config maps mounted into containers, whose mount point
+    /// is passed in an env var. An unset variable or a missing directory is skipped.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::fs;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir().join("konfik_config_dir_from_env");
+    /// fs::create_dir_all(dir.join("conf.d"))?;
+    /// fs::write(dir.join("config.yaml"), "host: localhost\nport: 80")?;
+    /// fs::write(dir.join("conf.d/10-port.json"), r#"{ "port": 8080 }"#)?;
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("KONFIK_DOC_CONFIG_DIR", &dir) };
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_dir_from_env("KONFIK_DOC_CONFIG_DIR")
+    ///     .with_config_dir_from_env("KONFIK_DOC_UNSET_CONFIG_DIR")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.host, "localhost");
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_dir_from_env(mut self, env_var: impl Into<String>) -> Self {
+        self.config_dir_env_vars.push(env_var.into());
+        self
+    }
+
+    /// Reject config keys that don't belong to any field with an [`Error::Validation`]
+    ///
+    /// The same check is enabled for a single type with `#[konfik(deny_unknown_fields)]`:
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// #[konfik(deny_unknown_fields)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_deny_unknown_fields.json");
+    /// std::fs::write(&path, r#"{ "port": 80, "prot": 8080 }"#)?;
+    ///
+    /// let result = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .load::<AppConfig>();
+    ///
+    /// assert!(matches!(result, Err(Error::Validation(_))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_deny_unknown_fields(mut self) -> Self {
+        self.deny_unknown_fields = true;
+        self
+    }
+
+    /// Add a custom source, merged after the config files and before env vars
+    ///
+    /// [`Priority::High`] layers and the file of
+    /// [`with_config_file_from_env`](Self::with_config_file_from_env) still override it.
+    ///
+    /// Custom sources are merged in the order they were added. They make konfik usable
+    /// where files and env vars aren't available, e.g. in a browser app that reads its
+    /// config from `localStorage` or a fetched JSON document. On targets without a
+    /// filesystem or process environment, like `wasm32-unknown-unknown`, the file and
+    /// env lookups find nothing and the rest of the pipeline works unchanged:
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik, config_meta::FieldMeta, config_source::ConfigSource};
+    /// use serde_json::Value;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     theme: String,
+    /// }
+    ///
+    /// struct LocalStorage(&'static str);
+    ///
+    /// impl ConfigSource for LocalStorage {
+    ///     fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+    ///         Ok(serde_json::from_str(self.0)?)
+    ///     }
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_source(LocalStorage(r#"{ "theme": "dark" }"#))
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.theme, "dark");
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_source<S>(mut self, source: S) -> Self
+    where
+        S: ConfigSource + Send + Sync + 'static,
+    {
+        self.sources.push((Arc::new(source), Priority::Normal));
+        self
+    }
+
+    /// Read a JSON secret from AWS Secrets Manager, requires the `cloud` feature
+    ///
+    /// Shorthand for [`with_source`](Self::with_source) with an [`AwsSecret`](crate::config_source::cloud::AwsSecret)
+    /// named by its name or ARN. The secret is fetched with the `aws` CLI and its credentials,
+    /// so credentials never have to be written to disk. Its values are merged like
+    /// [`with_source`](Self::with_source), after the config files and before env vars. Use [`AwsSecret`](crate::config_source::cloud::AwsSecret)
+    /// directly to pick a region.
+    ///
+    /// ```rust,no_run
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     database_url: String,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_aws_secret("myapp/prod")
+    ///     .load::<AppConfig>()?;
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[cfg(feature = "cloud")]
+    #[must_use]
+    pub fn with_aws_secret(self, secret_id: impl Into<String>) -> Self {
+        self.with_source(crate::config_source::cloud::AwsSecret::new(secret_id))
+    }
+
+    /// Read secret fields from Vault, requires the `vault` feature
+    ///
+    /// Shorthand for [`with_source_priority`](Self::with_source_priority) with a
+    /// [`VaultSecret`](crate::config_source::VaultSecret) at the API path `path`, e.g.
+    /// `secret/data/myapp`, and [`Priority::High`]. The values of the secret override every
+    /// config file and are overridden by env vars, so secrets like `database_url` don't have
+    /// to be stored in plaintext files.
+    ///
+    /// ```rust,no_run
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     database_url: String,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file("app.toml")
+    ///     .with_vault("secret/data/myapp")
+    ///     .load::<AppConfig>()?;
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[cfg(feature = "vault")]
+    #[must_use]
+    pub fn with_vault(self, path: impl Into<String>) -> Self {
+        self.with_source_priority(crate::config_source::VaultSecret::new(path), Priority::High)
+    }
+
+    /// Add a custom source with an explicit [`Priority`]
+    ///
+    /// Sources are ordered like [config layers](Self::with_config_layers), after the files
+    /// of the same priority. [`Priority::Low`] sources are merged before the regular config
+    /// files, so files override them. [`Priority::Normal`] sources are merged like
+    /// [`with_source`](Self::with_source), after the config files. [`Priority::High`]
+    /// sources are merged after every file, only env vars, CLI args and prompts override
+    /// them. Sources of the same priority are merged in the order they were added.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik, Priority, config_meta::FieldMeta, config_source::ConfigSource};
+    /// use serde_json::{Value, json};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     region: String,
+    ///     db_password: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// /// Looks up every field in a company secret store
+    /// struct SecretStore;
+    ///
+    /// impl ConfigSource for SecretStore {
+    ///     fn load(&self, fields: &[FieldMeta]) -> Result<Value, Error> {
+    ///         let mut values = serde_json::Map::new();
+    ///         for field in fields.iter().filter(|field| field.name == "db_password") {
+    ///             values.insert(field.path.clone(), json!("from-store"));
+    ///         }
+    ///         Ok(Value::Object(values))
+    ///     }
+    /// }
+    ///
+    /// struct Defaults;
+    ///
+    /// impl ConfigSource for Defaults {
+    ///     fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+    ///         Ok(json!({ "region": "eu-west-1", "db_password": "default" }))
+    ///     }
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_source_priority.toml");
+    /// std::fs::write(&path, "db_password = \"from-file\"\nport = 80")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_env_prefix("SOURCE_PRIO")
+    ///     .with_env_vars([("SOURCE_PRIO_PORT", "8080")])
+    ///     .with_source_priority(SecretStore, Priority::High)
+    ///     .with_source_priority(Defaults, Priority::Low)
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.region, "eu-west-1");
+    /// assert_eq!(config.db_password, "from-store");
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_source_priority<S>(mut self, source: S, priority: Priority) -> Self
+    where
+        S: ConfigSource + Send + Sync + 'static,
+    {
+        self.sources.push((Arc::new(source), priority));
+        self
+    }
+
+    /// Scrub the values at these dotted paths from every error message
+    ///
+    /// Deserialization and validation errors can echo the values they failed on. The values
+    /// of redacted paths are replaced with `[REDACTED]`, so a failed load doesn't leak
+    /// secrets into logs or crash reports. Errors konfik raises for a field, like a failed
+    /// deserialization, a pattern or a size, leave out the value of a redacted path
+    /// whatever its type.
+    ///
+    /// Messages of validators and custom sources are scrubbed by value instead. This
+    /// covers the values of every source, overridden ones included, and the values
+    /// [secrets](Self::with_secret_resolver) resolve to. Only string values are scrubbed,
+    /// where they aren't part of a longer word; numbers and bools can't be told apart from
+    /// e.g. line numbers.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     api_key: u64,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_redacted_paths.json");
+    /// std::fs::write(&path, r#"{ "api_key": "hunter2" }"#)?;
+    ///
+    /// let err = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_redacted_paths(&["api_key"])
+    ///     .load::<AppConfig>()
+    ///     .err()
+    ///     .map(|e| e.to_string())
+    ///     .unwrap_or_default();
+    ///
+    /// assert!(err.contains("[REDACTED]"));
+    /// assert!(!err.contains("hunter2"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_redacted_paths<S: AsRef<str>>(mut self, paths: &[S]) -> Self {
+        self.redacted_paths
+            .extend(paths.iter().map(|path| path.as_ref().to_string()));
+        self
+    }
+
+    /// Read dotted-path overrides from the env var `var`, e.g. `logging.level=debug;port=9000`
+    ///
+    /// Entries are split at `separator` and merged at env priority, after the per-field env
+    /// vars. Values are parsed like any other env value. The variable name is used as is,
+    /// without the env prefix. Malformed entries fail the load with an [`Error::Environment`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     #[konfik(nested)]
+    ///     logging: Logging,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Logging {
+    ///     level: String,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("MYAPP_OVERRIDE", "logging.level=debug;port=9000") };
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_env_override_var("MYAPP_OVERRIDE", ';')
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 9000);
+    /// assert_eq!(config.logging.level, "debug");
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_env_override_var(mut self, var: impl Into<String>, separator: char) -> Self {
+        self.env_override = Some((var.into(), separator));
+        self
+    }
+
+    /// Collect all env vars matching `pattern` into a map at the dotted `path`
+    ///
+    /// `pattern` is a full variable name, without the env prefix, containing one `*`. The
+    /// part matched by `*` becomes the lowercased map key. Values are parsed like any other
+    /// env value and merged at env priority, after the per-field env vars.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct ProxyConfig {
+    ///     upstreams: HashMap<String, String>,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe {
+    ///     std::env::set_var("MYAPP_UPSTREAM_API", "http://10.0.0.1");
+    ///     std::env::set_var("MYAPP_UPSTREAM_STATIC", "http://10.0.0.2");
+    ///     std::env::set_var("MYAPP_UPSTREAM_AUTH", "http://10.0.0.3");
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_env_glob("upstreams", "MYAPP_UPSTREAM_*")
+    ///     .load::<ProxyConfig>()?;
+    ///
+    /// assert_eq!(config.upstreams.len(), 3);
+    /// assert_eq!(config.upstreams["api"], "http://10.0.0.1");
+    /// assert_eq!(config.upstreams["static"], "http://10.0.0.2");
+    /// assert_eq!(config.upstreams["auth"], "http://10.0.0.3");
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_env_glob(mut self, path: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.env_globs.push((path.into(), pattern.into()));
+        self
+    }
+
+    /// Prompt on the terminal for required fields that are missing from all sources
+    ///
+    /// Answers are merged at CLI priority, empty answers leave the field missing. Input for
+    /// [redacted paths](Self::with_redacted_paths) is hidden. Nothing is prompted unless
+    /// stdin and stderr are terminals, so scripts and services fail as before.
+    ///
+    /// ```rust,no_run
+    /// # use konfik::{ConfigLoader, Konfik};
+    /// # #[derive(serde::Deserialize, Konfik)]
+    /// # struct AppConfig { database_url: String }
+    /// let config = ConfigLoader::default()
+    ///     .with_redacted_paths(&["database_url"])
+    ///     .with_interactive_prompts(true)
+    ///     .load::<AppConfig>()?;
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[cfg(feature = "prompt")]
+    #[must_use]
+    pub const fn with_interactive_prompts(mut self, enabled: bool) -> Self {
+        self.interactive_prompts = enabled;
+        self
+    }
+
+    /// Resolve `${path.to.field}` references to other config values
+    ///
+    /// References are resolved against the merged config of all sources. A string that is
+    /// a single reference takes the value of its target, including its type; otherwise the
+    /// target is formatted into the string. `$${` escapes a literal `${`. Unknown keys and
+    /// reference cycles fail the load with an [`Error::Validation`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     base_url: String,
+    ///     api_url: String,
+    ///     port: u16,
+    ///     admin_port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_value_references.toml");
+    /// std::fs::write(
+    ///     &path,
+    ///     r#"
+    ///     base_url = "https://example.com"
+    ///     api_url = "${base_url}/api"
+    ///     port = 8080
+    ///     admin_port = "${port}"
+    ///     "#,
+    /// )?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_value_references()
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.api_url, "https://example.com/api");
+    /// assert_eq!(config.admin_port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_value_references(mut self) -> Self {
+        self.value_references = true;
+        self
+    }
+
+    /// Resolve `secret://<name>` strings in the merged config with `resolver`
+    ///
+    /// The config only names the secret, where it's stored is up to the resolver, e.g. a
+    /// vault client, the OS keychain or a map of env vars. References are resolved after
+    /// merging and [value references](Self::with_value_references), before validation.
+    /// A reference the resolver returns `None` for fails the load with an
+    /// [`Error::Validation`]. Resolved secrets are treated like the values at
+    /// [redacted paths](Self::with_redacted_paths): they are scrubbed from errors and
+    /// replaced in [`doctor`](Self::doctor), [`load_and_dump`](Self::load_and_dump) and
+    /// `--print-config`. At redacted paths the reference is scrubbed from errors as well.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     user: String,
+    ///     password: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_secret_resolver.toml");
+    /// std::fs::write(&path, "user = \"app\"\npassword = \"secret://db_password\"")?;
+    ///
+    /// let loader = ConfigLoader::default().with_config_file(&path);
+    ///
+    /// let config = loader
+    ///     .clone()
+    ///     .with_secret_resolver(|name| (name == "db_password").then(|| "hunter2".to_string()))
+    ///     .load::<AppConfig>()?;
+    /// assert_eq!(config.password, "hunter2");
+    ///
+    /// let result = loader.with_secret_resolver(|_| None).load::<AppConfig>();
+    /// assert!(matches!(result, Err(Error::Validation(_))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_secret_resolver<F>(mut self, resolver: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.secret_resolver = Some(Arc::new(resolver));
+        self
+    }
+
+    /// Delete keys set by lower layers when a higher layer sets them to `sentinel`
+    ///
+    /// Merging can only add or replace values, so without a sentinel a layer can't remove
+    /// an inherited key, e.g. clear a list or drop a whole table. Any source can delete keys
+    /// this way, the sentinel itself never ends up in the merged config.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    ///     replica: Option<String>,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     plugins: Option<Vec<String>>,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    /// }
+    ///
+    /// let base = std::env::temp_dir().join("konfik_delete_sentinel_base.toml");
+    /// let local = std::env::temp_dir().join("konfik_delete_sentinel_local.toml");
+    /// std::fs::write(
+    ///     &base,
+    ///     "plugins = [\"metrics\"]\n[database]\nurl = \"postgres://db\"\nreplica = \"postgres://replica\"",
+    /// )?;
+    /// std::fs::write(&local, "plugins = \"__delete__\"\n[database]\nreplica = \"__delete__\"")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&base)
+    ///     .with_config_file(&local)
+    ///     .with_delete_sentinel("__delete__")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.plugins, None);
+    /// assert_eq!(config.database.url, "postgres://db");
+    /// assert_eq!(config.database.replica, None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_delete_sentinel(mut self, sentinel: impl Into<serde_json::Value>) -> Self {
+        self.delete_sentinel = Some(sentinel.into());
+        self
+    }
+
+    /// Set how arrays at the same key are merged (default: [`ArrayMerge::Replace`])
+    ///
+    /// With [`ArrayMerge::Append`] a `Vec` field collects the items of every source, lower
+    /// priority sources first, instead of only keeping the array of the highest priority
+    /// one. This applies at any depth. Env vars and CLI args follow the same rule: a value
+    /// that is an array, like `APP_ORIGINS='["b"]'`, is appended, while a scalar value
+    /// still replaces the array.
+    ///
+    /// ```rust
+    /// use konfik::{ArrayMerge, ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     allowed_origins: Vec<String>,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_array_merge.toml");
+    /// std::fs::write(&path, "allowed_origins = [\"https://a.example\"]")?;
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("ARRAY_MERGE_ALLOWED_ORIGINS", r#"["https://b.example"]"#) };
+    ///
+    /// let loader = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_env_prefix("ARRAY_MERGE");
+    ///
+    /// let replaced = loader.clone().load::<AppConfig>()?;
+    /// assert_eq!(replaced.allowed_origins, ["https://b.example"]);
+    ///
+    /// let appended = loader.with_array_merge(ArrayMerge::Append).load::<AppConfig>()?;
+    /// assert_eq!(appended.allowed_origins, ["https://a.example", "https://b.example"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_array_merge(mut self, strategy: ArrayMerge) -> Self {
+        self.array_merge = strategy;
+        self
+    }
+
+    /// Parse quoted booleans and numbers in config files for fields of those types
+    ///
+    /// A hand edited `port = "8080"` or `debug: "true"` otherwise fails to deserialize into
+    /// a `u16` or `bool`. The strings are parsed like env values, but only for fields typed as
+    /// booleans, integers or floats, so genuine string fields keep their quotes. Off by default.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     debug: bool,
+    ///     version: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_coerce_scalar_strings.yaml");
+    /// std::fs::write(&path, "port: \"8080\"\ndebug: \"true\"\nversion: \"2\"")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_coerce_scalar_strings(true)
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert!(config.debug);
+    /// assert_eq!(config.version, "2");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_coerce_scalar_strings(mut self, coerce: bool) -> Self {
+        self.coerce_scalar_strings = coerce;
+        self
+    }
+
+    /// Accept custom literals for `bool` fields, e.g. `Y`/`N` or `enabled`/`disabled`
+    ///
+    /// String values of `bool` fields, from env vars, files or CLI args, are compared to the
+    /// literals ignoring ASCII case. `true` and `false` are still accepted. Any other string
+    /// fails the load with an [`Error::Validation`](crate::Error::Validation) that lists the
+    /// accepted literals. clap parses `bool` flags itself, so this only affects CLI args that
+    /// take a string value.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     metrics: bool,
+    ///     tracing: Option<bool>,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe {
+    ///     std::env::set_var("BOOL_LITERALS_METRICS", "Y");
+    ///     std::env::set_var("BOOL_LITERALS_TRACING", "disabled");
+    /// }
+    ///
+    /// let loader = ConfigLoader::default()
+    ///     .with_env_prefix("BOOL_LITERALS")
+    ///     .with_bool_literals(&["y", "enabled"], &["n", "disabled"]);
+    ///
+    /// let config = loader.load::<AppConfig>()?;
+    /// assert!(config.metrics);
+    /// assert_eq!(config.tracing, Some(false));
+    ///
+    /// // SAFETY: see above
+    /// unsafe { std::env::set_var("BOOL_LITERALS_METRICS", "maybe") };
+    /// let result = loader.load::<AppConfig>();
+    /// assert!(matches!(result, Err(Error::Validation(message)) if message.contains("metrics")));
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_bool_literals<S: AsRef<str>>(
+        mut self,
+        true_values: &[S],
+        false_values: &[S],
+    ) -> Self {
+        let literals = |values: &[S]| {
+            values
+                .iter()
+                .map(|value| value.as_ref().to_string())
+                .collect()
+        };
+        self.bool_literals = Some((literals(true_values), literals(false_values)));
+        self
+    }
+
+    /// Match keys in config files to the fields of the config regardless of case
+    ///
+    /// A hand edited `Port = 8080` is loaded into the field `port`. Keys without a field
+    /// are kept as they are. Two keys of one table that only differ in case, like `Port` and
+    /// `port`, are ambiguous and fail the load with an [`Error::Validation`]. Off by default.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_case_insensitive_keys.toml");
+    /// std::fs::write(&path, "Port = 8080\n[DATABASE]\nUrl = \"postgres://db\"")?;
+    ///
+    /// let loader = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_case_insensitive_keys(true);
+    /// let config = loader.load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(config.database.url, "postgres://db");
+    ///
+    /// std::fs::write(&path, "Port = 8080\nport = 80\n[database]\nurl = \"postgres://db\"")?;
+    /// assert!(loader.load::<AppConfig>().is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_case_insensitive_keys(mut self, enabled: bool) -> Self {
+        self.case_insensitive_keys = enabled;
+        self
+    }
+
+    /// Round floats to `decimals` decimal places when [dumping](Self::dump) configs
+    ///
+    /// Floats that went through an `f32` pick up representation noise, e.g. `0.1` is dumped
+    /// as `0.10000000149011612`. Rounding keeps dumps readable and diffs minimal, at the cost
+    /// of exactness: values with more significant decimals than `decimals` are changed.
+    /// Without this option floats are dumped exactly.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, FileFormat};
+    /// use serde_json::json;
+    ///
+    /// let config = json!({ "ratio": f64::from(0.1_f32) });
+    /// let loader = ConfigLoader::default();
+    ///
+    /// assert_eq!(loader.dump(&config, FileFormat::Toml)?, "ratio = 0.10000000149011612\n");
+    /// assert_eq!(
+    ///     loader.with_float_precision(6).dump(&config, FileFormat::Toml)?,
+    ///     "ratio = 0.1\n"
+    /// );
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_float_precision(mut self, decimals: usize) -> Self {
+        self.float_precision = Some(decimals);
+        self
+    }
+
+    /// Read secrets from the systemd credentials in `$CREDENTIALS_DIRECTORY`
+    ///
+    /// Every credential file whose name matches a field is merged after the env vars, so
+    /// credentials override files and env vars but not CLI args. A credential name matches
+    /// the field with the same dotted path, compared case-insensitively with `-` treated as
+    /// `_`: `database.password` and `Database.Password` both set `database.password`, and
+    /// `api-key` sets `api_key`. Use [`with_systemd_credential`](Self::with_systemd_credential)
+    /// for other names. Credentials that match no field are ignored. Nothing happens if
+    /// `CREDENTIALS_DIRECTORY` is unset or doesn't exist. Values are parsed like env values.
+    /// Their source is [`Source::Credential`], e.g. for [`doctor`](Self::doctor) and
+    /// [`with_validation_on`](Self::with_validation_on).
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     api_key: String,
+    ///     db_password: String,
+    /// }
+    ///
+    /// let dir = std::env::temp_dir().join("konfik_credentials");
+    /// std::fs::create_dir_all(&dir)?;
+    /// std::fs::write(dir.join("api-key"), "abc123\n")?;
+    /// std::fs::write(dir.join("postgres"), "hunter2")?;
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("CREDENTIALS_DIRECTORY", &dir) };
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_systemd_credentials()
+    ///     .with_systemd_credential("postgres", "db_password")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.api_key, "abc123");
+    /// assert_eq!(config.db_password, "hunter2");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_systemd_credentials(mut self) -> Self {
+        self.systemd_credentials.get_or_insert_default();
+        self
+    }
+
+    /// Map the systemd credential `name` to the field at the dotted `path`
+    ///
+    /// Enables [`with_systemd_credentials`](Self::with_systemd_credentials).
+    #[must_use]
+    pub fn with_systemd_credential(
+        mut self,
+        name: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        self.systemd_credentials
+            .get_or_insert_default()
+            .insert(name.into(), path.into());
+        self
+    }
+
+    /// Only read the `namespace` section of config files
+    ///
+    /// Lets several tools share one config file, each with its own section. Keys outside
+    /// the section are ignored. Files without the section are used as a whole, unless
+    /// [`with_required_namespace`](Self::with_required_namespace) is set. Env vars and
+    /// CLI args are not namespaced.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_namespace.toml");
+    /// std::fs::write(&path, "[tool_a]\nport = 80\n[tool_b]\nport = 8080")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_namespace("tool_b")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Only read the section named after the running binary from config files
+    ///
+    /// The name is the file stem of [`std::env::current_exe`], e.g. `tool_a` for
+    /// `/usr/bin/tool_a`. Nothing changes if it can't be determined. See
+    /// [`with_namespace`](Self::with_namespace).
+    #[must_use]
+    pub fn with_binary_namespace(self) -> Self {
+        let binary = std::env::current_exe().ok().and_then(|exe| {
+            exe.file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(str::to_string)
+        });
+
+        match binary {
+            Some(binary) => self.with_namespace(binary),
+            None => self,
+        }
+    }
+
+    /// Fail with an [`Error::Validation`] if a config file has no section for the namespace
+    #[must_use]
+    pub const fn with_required_namespace(mut self) -> Self {
+        self.namespace_required = true;
+        self
+    }
+
+    /// Merge the `profiles` sections of config files, later profiles override earlier ones
+    ///
+    /// Lets profiles extend each other, e.g. `prod` only lists what differs from `base`.
+    /// Sections missing from a file are skipped. Files without any of the sections are
+    /// used as a whole. Profiles are selected after the namespace section was extracted.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    ///     debug: bool,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_profiles.toml");
+    /// std::fs::write(
+    ///     &path,
+    ///     "[base]\nhost = \"localhost\"\nport = 80\ndebug = true\n[prod]\nhost = \"example.com\"\ndebug = false",
+    /// )?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_profiles(&["base", "prod", "missing"])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.host, "example.com");
+    /// assert_eq!(config.port, 80);
+    /// assert!(!config.debug);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_profiles<S: AsRef<str>>(mut self, profiles: &[S]) -> Self {
+        self.profiles
+            .extend(profiles.iter().map(|profile| profile.as_ref().to_string()));
+        self
+    }
+
+    /// Fill in `defaults` at every path that is absent after merging all sources
+    ///
+    /// Unlike a config file, the defaults never override anything: they are applied as
+    /// the last step before validation and only insert keys that no source set. Filled in
+    /// values are attributed to [`Source::Default`]. Calling this again adds more defaults,
+    /// later ones win.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use serde_json::json;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_fill_defaults.toml");
+    /// std::fs::write(&path, "port = 8080")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_fill_defaults(json!({ "host": "localhost", "port": 80 }))
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.host, "localhost");
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_fill_defaults(mut self, defaults: serde_json::Value) -> Self {
+        self.fill_defaults = Some(match self.fill_defaults.take() {
+            Some(existing) => crate::merge::merge(existing, defaults),
+            None => defaults,
+        });
+        self
+    }
+
+    /// Require every field to be set by a source, defaults aren't enough
+    ///
+    /// For audited deployments that forbid implicit configuration: fields falling back to
+    /// `#[serde(default)]`, `default_if_none` or [fill defaults](Self::with_fill_defaults),
+    /// and absent `Option` fields, fail the load with an [`Error::Validation`] listing them.
+    /// Skipped fields are exempt.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     #[serde(default)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_require_explicit.toml");
+    /// std::fs::write(&path, "host = \"localhost\"")?;
+    ///
+    /// let loader = ConfigLoader::default().with_config_file(&path);
+    /// assert!(loader.load::<AppConfig>().is_ok());
+    ///
+    /// let result = loader.with_require_explicit(true).load::<AppConfig>();
+    /// let Err(Error::Validation(message)) = result else { panic!("expected an error") };
+    /// assert_eq!(message, "fields without an explicit value: port");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_require_explicit(mut self, required: bool) -> Self {
+        self.require_explicit = required;
+        self
+    }
+
+    /// Trim surrounding whitespace from env values before parsing them (default: `true`)
+    ///
+    /// Values injected from secret files often end with a newline. Disable trimming
+    /// if a value legitimately needs its surrounding whitespace.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("ENV_TRIM_PORT", "8080\n") };
+    ///
+    /// let loader = ConfigLoader::default().with_env_prefix("ENV_TRIM");
+    ///
+    /// assert_eq!(loader.clone().load::<AppConfig>()?.port, 8080);
+    /// assert!(loader.with_env_trim(false).load::<AppConfig>().is_err());
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_env_trim(mut self, trim: bool) -> Self {
+        self.env_trim = trim;
+        self
+    }
+
+    /// Only read the env vars in `vars`, no other env var is looked at
+    ///
+    /// Fields and [env globs](Self::with_env_glob) are only loaded from allowlisted vars,
+    /// even if their derived name is set in the environment. Instead of scanning the whole
+    /// environment, only the allowlisted vars are read. Env vars passed to the loader by
+    /// name, like the [override var](Self::with_env_override_var), are still read.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     host: Option<String>,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe {
+    ///     std::env::set_var("ALLOW_PORT", "8080");
+    ///     std::env::set_var("ALLOW_HOST", "example.com");
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_env_prefix("ALLOW")
+    ///     .with_env_allowlist(&["ALLOW_PORT"])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(config.host, None);
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_env_allowlist<S: AsRef<str>>(mut self, vars: &[S]) -> Self {
+        self.env_allowlist
+            .get_or_insert_with(Vec::new)
+            .extend(vars.iter().map(|var| var.as_ref().to_string()));
+        self
+    }
+
+    /// Read env vars from `vars` instead of the process environment
+    ///
+    /// Everything the loader reads from the environment comes from `vars` then: fields,
+    /// [env globs](Self::with_env_glob), and the env vars passed to the loader by name, like
+    /// the [override var](Self::with_env_override_var). Together with
+    /// [`with_cli_args`](Self::with_cli_args) this makes loads independent of the process,
+    /// e.g. in tests running in parallel.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_env_prefix("INJECTED")
+    ///     .with_env_vars([("INJECTED_PORT", "8080")])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<OsString>,
+    {
+        self.env_vars = Some(
+            vars.into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Load an additional config from a location given by a CLI flag or env var
+    ///
+    /// The location is read from `--<flag> <location>` (or `--<flag>=<location>`) and
+    /// otherwise from the env var `env_var`, which is used as is without the prefix.
+    /// It can be a file path, a `file://` URL or, with the `remote` feature, a
+    /// `http(s)://` URL. The format is inferred from the extension and defaults to JSON.
+    ///
+    /// The referenced config is merged after the other config files, so it takes
+    /// precedence over them but not over [`Priority::High`] layers, the file of
+    /// [`with_config_file_from_env`](Self::with_config_file_from_env), env vars and CLI args. It is resolved once
+    /// before the merge, a referenced config can't point to yet another location.
+    /// A location that doesn't exist is an error.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_config_location.toml");
+    /// std::fs::write(&path, "port = 8080")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_location("KONFIK_DOC_CONFIG_URL", "config-url")
+    ///     .with_cli_args(["myapp".into(), format!("--config-url={}", path.display())])
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_config_location(
+        mut self,
+        env_var: impl Into<String>,
+        flag: impl Into<String>,
+    ) -> Self {
+        self.config_location = Some((env_var.into(), flag.into()));
+        self
+    }
+
+    /// Fetch a config document over HTTP(S), requires the `remote` feature
+    ///
+    /// The format is taken from the `Content-Type` of the response (`application/json`,
+    /// `application/yaml`, `application/toml`, ...), otherwise from the extension of the
+    /// URL and defaults to JSON. Remote configs are merged after the config files in the
+    /// order they were added, so they override the files but not the
+    /// [config location](Self::with_config_location), [`Priority::High`] layers, the file of
+    /// [`with_config_file_from_env`](Self::with_config_file_from_env), env vars or CLI args. Unlike a
+    /// missing file, a failed request is an [`Error::Remote`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "remote")]
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::{
+    ///     io::{BufRead, BufReader, Write},
+    ///     net::TcpListener,
+    ///     time::Duration,
+    /// };
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// let url = format!("http://{}/app", listener.local_addr()?);
+    /// let server = std::thread::spawn(move || -> std::io::Result<bool> {
+    ///     let (stream, _) = listener.accept()?;
+    ///     let mut authorized = false;
+    ///     for line in BufReader::new(&stream).lines() {
+    ///         let line = line?;
+    ///         authorized |= line.eq_ignore_ascii_case("authorization: Bearer secret");
+    ///         if line.is_empty() {
+    ///             break;
+    ///         }
+    ///     }
+    ///     let body = "port = 8080";
+    ///     write!(
+    ///         &stream,
+    ///         "HTTP/1.1 200 OK\r\ncontent-type: application/toml\r\ncontent-length: {}\r\n\r\n{body}",
+    ///         body.len()
+    ///     )?;
+    ///     Ok(authorized)
+    /// });
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_remote_url(url)
+    ///     .with_remote_timeout(Duration::from_secs(5))
+    ///     .with_remote_header("Authorization", "Bearer secret")
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert!(server.join().expect("server panicked")?);
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "remote"))]
+    /// # fn main() {}
+    /// ```
+    #[must_use]
+    pub fn with_remote_url(mut self, url: impl Into<String>) -> Self {
+        self.remote_urls.push(url.into());
+        self
+    }
+
+    /// Set the timeout of remote requests, including connecting (default: none)
+    ///
+    /// Applies to [remote URLs](Self::with_remote_url) and `http(s)://`
+    /// [config locations](Self::with_config_location).
+    #[must_use]
+    pub const fn with_remote_timeout(mut self, timeout: Duration) -> Self {
+        self.remote_timeout = Some(timeout);
+        self
+    }
+
+    /// Send a header with every remote request, e.g. `Authorization`
+    ///
+    /// Applies to [remote URLs](Self::with_remote_url) and `http(s)://`
+    /// [config locations](Self::with_config_location). Header values aren't shown in
+    /// the `Debug` output of the loader.
+    #[must_use]
+    pub fn with_remote_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.remote_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Call `f` whenever a higher priority source overrides a different value
+    ///
+    /// `f` receives the dotted path, the old value, the new value and the source of
+    /// the new value. Values that are overridden by an equal value aren't reported.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Source};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_conflict_handler.toml");
+    /// std::fs::write(&path, "port = 8080")?;
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("CONFLICT_PORT", "9000") };
+    ///
+    /// let conflicts = Arc::new(Mutex::new(Vec::new()));
+    /// let log = Arc::clone(&conflicts);
+    ///
+    /// ConfigLoader::default()
+    ///     .with_env_prefix("CONFLICT")
+    ///     .with_config_file(&path)
+    ///     .with_conflict_handler(move |path, old, new, source| {
+    ///         log.lock().expect("poisoned").push(format!("{source:?} overrode {path} from {old} to {new}"));
+    ///     })
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(*conflicts.lock().expect("poisoned"), ["Env overrode port from 8080 to 9000"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_conflict_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, &serde_json::Value, &serde_json::Value, Source) + Send + Sync + 'static,
+    {
+        self.conflict_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Call `f` with warnings about the loaded config
+    ///
+    /// Warnings don't stop loading. Currently they report values set under a
+    /// deprecated name (`#[konfik(deprecated_alias = "old")]`), which are moved
+    /// to the field's current name. Without a handler, warnings are dropped.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     #[konfik(deprecated_alias = "db_url")]
+    ///     database_url: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_warning_handler.toml");
+    /// std::fs::write(&path, "db_url = \"postgres://localhost\"")?;
+    ///
+    /// let warnings = Arc::new(Mutex::new(Vec::new()));
+    /// let log = Arc::clone(&warnings);
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_warning_handler(move |warning| log.lock().expect("poisoned").push(warning.to_string()))
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.database_url, "postgres://localhost");
+    /// assert_eq!(*warnings.lock().expect("poisoned"), ["`db_url` is deprecated, use `database_url` instead"]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_warning_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.warning_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the key holding the name of the CLI subcommand, `_subcommand` by default
+    ///
+    /// A field marked `#[command(subcommand)]` receives the args of the subcommand
+    /// together with its name under this key, so it deserializes into an internally
+    /// tagged enum with a matching `#[serde(tag = "...")]`. clap names subcommands in
+    /// kebab-case, so the enum usually needs `#[serde(rename_all = "kebab-case")]`.
+    ///
+    /// Without a subcommand field, the name is stored under this key at the top level
+    /// and the args under a key named after the subcommand.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct Git {
+    ///     #[command(subcommand)]
+    ///     command: Command,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, clap::Subcommand, Debug, PartialEq)]
+    /// #[serde(tag = "command", rename_all = "kebab-case")]
+    /// enum Command {
+    ///     Clone { url: String },
+    ///     Log { #[arg(long)] limit: u32 },
+    /// }
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_subcommand_tag("command")
+    ///     .with_cli_args(["git", "log", "--limit", "5"])
+    ///     .load_with_cli::<Git>()?;
+    ///
+    /// assert_eq!(config.command, Command::Log { limit: 5 });
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    #[must_use]
+    pub fn with_subcommand_tag(mut self, tag: impl Into<String>) -> Self {
+        self.subcommand_tag = tag.into();
         self
     }
 
@@ -77,9 +2134,359 @@ impl ConfigLoader {
     #[must_use]
     pub fn with_validation<F>(mut self, f: F) -> Self
     where
-        F: Fn(&serde_json::Value) -> Result<(), Error> + 'static,
+        F: Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync + 'static,
     {
-        self.validation = Some(Box::new(f));
+        self.validation = Some(Arc::new(f));
+        self
+    }
+
+    /// Add validation function that only sees values originating from `source`
+    ///
+    /// The validator receives the merged config reduced to the values that were
+    /// provided by `source` and not overridden by a higher priority source.
+    /// Source validators run before the validator set with [`Self::with_validation`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik, Source};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("VALIDATE_ON_PORT", "80") };
+    ///
+    /// let result = ConfigLoader::default()
+    ///     .with_env_prefix("VALIDATE_ON")
+    ///     .with_validation_on(Source::Env, |env| {
+    ///         match env.get("port").and_then(serde_json::Value::as_u64) {
+    ///             Some(port) if port < 1024 => Err(Error::Validation("privileged port".into())),
+    ///             _ => Ok(()),
+    ///         }
+    ///     })
+    ///     .load::<AppConfig>();
+    ///
+    /// assert!(matches!(result, Err(Error::Validation(_))));
+    /// ```
+    #[must_use]
+    pub fn with_validation_on<F>(mut self, source: Source, f: F) -> Self
+    where
+        F: Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.source_validation.push((source, Arc::new(f)));
+        self
+    }
+
+    /// Add a validation function that reacts to failures according to `mode`
+    ///
+    /// These validators run in the order they were added, after the validator set with
+    /// [`Self::with_validation`]. Failures of [`ValidationMode::Collect`] validators are
+    /// collected and validation continues; a failing [`ValidationMode::FailFast`] validator
+    /// stops it. A single failure is returned as is, several as an [`Error::Multiple`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik, ValidationMode};
+    /// use std::sync::{
+    ///     Arc,
+    ///     atomic::{AtomicBool, Ordering},
+    /// };
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     #[serde(default)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let fail = |message: &'static str| move |_: &serde_json::Value| Err(Error::Validation(message.into()));
+    /// let expensive_ran = Arc::new(AtomicBool::new(false));
+    /// let ran = Arc::clone(&expensive_ran);
+    ///
+    /// let result = ConfigLoader::default()
+    ///     .with_validation_mode(ValidationMode::Collect, fail("port is zero"))
+    ///     .with_validation_mode(ValidationMode::Collect, |_| Ok(()))
+    ///     .with_validation_mode(ValidationMode::FailFast, fail("host unreachable"))
+    ///     .with_validation_mode(ValidationMode::FailFast, move |_| {
+    ///         ran.store(true, Ordering::Relaxed);
+    ///         Ok(())
+    ///     })
+    ///     .load::<AppConfig>();
+    ///
+    /// let Err(Error::Multiple(errors)) = result else { panic!("expected multiple errors") };
+    /// assert_eq!(errors.len(), 2);
+    /// assert!(!expensive_ran.load(Ordering::Relaxed));
+    /// ```
+    #[must_use]
+    pub fn with_validation_mode<F>(mut self, mode: ValidationMode, f: F) -> Self
+    where
+        F: Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.mode_validation.push((mode, Arc::new(f)));
+        self
+    }
+
+    /// Add a validation function that only runs in the environment `environment`
+    ///
+    /// The active environment is the value of the env var set with
+    /// [`with_environment_var`](Self::with_environment_var), or else the last profile set
+    /// with [`with_profiles`](Self::with_profiles). Without an active environment these
+    /// validators don't run. They run after the validator set with [`Self::with_validation`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     debug: bool,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_validation_for_env.toml");
+    /// std::fs::write(&path, "debug = true")?;
+    ///
+    /// let load = || {
+    ///     ConfigLoader::default()
+    ///         .with_config_file(&path)
+    ///         .with_environment_var("KONFIK_DOC_ENVIRONMENT")
+    ///         .with_validation_for_env("prod", |config| match config["debug"].as_bool() {
+    ///             Some(true) => Err(Error::Validation("debug is forbidden in prod".into())),
+    ///             _ => Ok(()),
+    ///         })
+    ///         .load::<AppConfig>()
+    /// };
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("KONFIK_DOC_ENVIRONMENT", "dev") };
+    /// assert!(load().is_ok());
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("KONFIK_DOC_ENVIRONMENT", "prod") };
+    /// assert!(matches!(load(), Err(Error::Validation(_))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_validation_for_env<F>(mut self, environment: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(&serde_json::Value) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.env_validation.push((environment.into(), Arc::new(f)));
+        self
+    }
+
+    /// Read the active environment for [`with_validation_for_env`](Self::with_validation_for_env)
+    /// from the env var `var`, used as is without the env prefix
+    #[must_use]
+    pub fn with_environment_var(mut self, var: impl Into<String>) -> Self {
+        self.environment_var = Some(var.into());
+        self
+    }
+
+    /// Add a validation function that runs against every config file before it is merged
+    ///
+    /// `f` receives the path (or URL) of the file and its parsed content, after the
+    /// namespace was extracted. Use it for policies about single files that can't be
+    /// checked on the merged config. Errors are wrapped in an [`Error::InvalidFile`]
+    /// naming the file.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     debug: bool,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_per_file_production.toml");
+    /// std::fs::write(&path, "debug = true")?;
+    ///
+    /// let result = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_per_file_validation(|path, value| {
+    ///         let production = path.to_string_lossy().contains("production");
+    ///         if production && value["debug"] == true {
+    ///             return Err(Error::Validation("debug must be off in production".into()));
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .load::<AppConfig>();
+    ///
+    /// let Err(Error::InvalidFile { path: invalid, .. }) = result else { panic!("expected an invalid file") };
+    /// assert_eq!(invalid, path);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_per_file_validation<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Path, &serde_json::Value) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        self.file_validation.push(Arc::new(f));
+        self
+    }
+
+    /// Add a migration from config version `from_version` to the next version
+    ///
+    /// Before defaults are filled in, the `version` key of the merged config is read, a
+    /// missing version is version 1. Migrations run in sequence, starting at that version, until
+    /// there is none for the current version. After each migration `version` is set to
+    /// the next version. Errors of migrations fail the load with an [`Error::Validation`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    /// use serde_json::{Value, json};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     version: u64,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_migration.json");
+    /// std::fs::write(&path, r#"{ "db": "postgres://db" }"#)?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     // v1 -> v2: `db` was renamed to `db_url`
+    ///     .with_migration(1, |mut config| {
+    ///         let url = config["db"].take();
+    ///         config["db_url"] = url;
+    ///         Ok(config)
+    ///     })
+    ///     // v2 -> v3: `db_url` moved to `database.url`
+    ///     .with_migration(2, |mut config| {
+    ///         let url = config["db_url"].take();
+    ///         config["database"] = json!({ "url": url });
+    ///         Ok(config)
+    ///     })
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.version, 3);
+    /// assert_eq!(config.database.url, "postgres://db");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_migration<F>(mut self, from_version: u64, f: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Result<serde_json::Value, Error> + Send + Sync + 'static,
+    {
+        self.migrations.push((from_version, Arc::new(f)));
+        self
+    }
+
+    /// Add a hook that runs with the typed config after it was successfully loaded
+    ///
+    /// Use it to set up subsystems that depend on the config, e.g. a logger.
+    /// Returning an error aborts the load with that error. Hooks run in the
+    /// order they were added and only when loading the type they accept, loading other
+    /// types skips them. Hooks can't accept types with generic parameters, adding one fails
+    /// to compile, see [`ConfigMeta::as_any`](crate::config_meta::ConfigMeta::as_any).
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::sync::{
+    ///     Arc,
+    ///     atomic::{AtomicBool, Ordering},
+    /// };
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     #[serde(default)]
+    ///     level: String,
+    /// }
+    ///
+    /// let initialized = Arc::new(AtomicBool::new(false));
+    /// let flag = Arc::clone(&initialized);
+    ///
+    /// ConfigLoader::default()
+    ///     .with_post_load(move |_config: &AppConfig| {
+    ///         flag.store(true, Ordering::Relaxed);
+    ///         Ok(())
+    ///     })
+    ///     .load::<AppConfig>()?;
+    ///
+    /// assert!(initialized.load(Ordering::Relaxed));
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// # use konfik::{ConfigLoader, Konfik};
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct Wrapper<T> {
+    ///     value: T,
+    /// }
+    ///
+    /// let loader = ConfigLoader::default().with_post_load(|_: &Wrapper<u16>| Ok(()));
+    /// ```
+    #[must_use]
+    pub fn with_post_load<T, F>(mut self, f: F) -> Self
+    where
+        T: ConfigMeta + 'static,
+        F: Fn(&T) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        const {
+            assert!(
+                T::HAS_ANY,
+                "post load hooks can't accept configs with generic parameters"
+            );
+        }
+        let hook = move |config: &dyn Any| config.downcast_ref::<T>().map_or(Ok(()), &f);
+        self.post_load.push((TypeId::of::<T>(), Arc::new(hook)));
+        self
+    }
+
+    /// Keep the fields at `paths` from changing after the first config of a [subscription](Self::subscribe)
+    ///
+    /// Some settings, like a listen port, can't be changed while the process is running.
+    /// Updates changing the merged value at one of the paths are rejected with an
+    /// [`Error::Validation`] by [`ImmutableMode::Reject`], the subscription continues with
+    /// the next update. [`ImmutableMode::KeepOriginal`] accepts the update but keeps the
+    /// value of the first config, or keeps the field absent if the first config didn't set
+    /// it. Regular loads aren't affected.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, ImmutableMode, Konfik, config_source::ChannelSource};
+    /// use serde_json::json;
+    /// use std::sync::mpsc;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     workers: u8,
+    /// }
+    ///
+    /// let updates = || -> Result<_, Box<dyn std::error::Error>> {
+    ///     let (sender, receiver) = mpsc::channel();
+    ///     sender.send(json!({ "port": 80, "workers": 2 }))?;
+    ///     sender.send(json!({ "port": 8080, "workers": 4 }))?;
+    ///     Ok(ChannelSource::new(receiver))
+    /// };
+    ///
+    /// let rejecting = ConfigLoader::default().with_immutable_fields(&["port"], ImmutableMode::Reject);
+    /// let mut configs = rejecting.subscribe::<AppConfig, _>(updates()?);
+    /// assert_eq!(configs.next().transpose()?.map(|config| config.port), Some(80));
+    /// assert!(configs.next().is_some_and(|config| config.is_err()));
+    ///
+    /// let keeping =
+    ///     ConfigLoader::default().with_immutable_fields(&["port"], ImmutableMode::KeepOriginal);
+    /// let mut configs = keeping.subscribe::<AppConfig, _>(updates()?);
+    /// configs.next().transpose()?;
+    /// let reloaded = configs.next().transpose()?.ok_or("no reload")?;
+    /// assert_eq!((reloaded.port, reloaded.workers), (80, 4));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn with_immutable_fields<S: AsRef<str>>(
+        mut self,
+        paths: &[S],
+        mode: ImmutableMode,
+    ) -> Self {
+        self.immutable_fields
+            .extend(paths.iter().map(|path| path.as_ref().to_string()));
+        self.immutable_mode = mode;
         self
     }
 }
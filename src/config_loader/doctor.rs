@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::{ConfigReport, Error, FieldReport, Source, config_meta::ConfigMeta};
+use serde_json::{Map, Value};
+
+impl ConfigLoader {
+    /// Explains how every field of `T` is resolved from the configured sources
+    ///
+    /// The sources are merged and validated like in [`load`](Self::load), but instead of
+    /// failing, every problem ends up in the returned [`ConfigReport`]. Next to its final
+    /// value, each field lists the source it came from and whether it's a default or
+    /// overrode the value of another source. Values at
+    /// [redacted paths](Self::with_redacted_paths) and resolved
+    /// [secrets](Self::with_secret_resolver) are replaced. CLI args aren't considered.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Source};
+    /// use serde_json::json;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    ///     api_key: String,
+    ///     token: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_doctor.toml");
+    /// std::fs::write(&path, "host = \"localhost\"\nport = 80\napi_key = \"secret\"")?;
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("DOCTOR_PORT", "8080") };
+    ///
+    /// let report = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_env_prefix("DOCTOR")
+    ///     .with_redacted_paths(&["api_key"])
+    ///     .doctor::<AppConfig>();
+    ///
+    /// let port = report.field("port").unwrap();
+    /// assert_eq!(port.value, Some(json!(8080)));
+    /// assert_eq!(port.source, Some(Source::Env));
+    /// assert!(port.overridden);
+    ///
+    /// assert_eq!(report.field("api_key").unwrap().value, Some(json!("[REDACTED]")));
+    /// assert!(!report.field("token").unwrap().warnings.is_empty());
+    /// assert!(!report.is_ok());
+    ///
+    /// println!("{report}");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn doctor<T: ConfigMeta>(&self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+        let mut provenance = Provenance::default();
+
+        let mut config = self.load_sources::<T>(&mut provenance).unwrap_or_else(|e| {
+            report.warnings.push(e.to_string());
+            Value::Object(Map::new())
+        });
+
+        Self::apply_precedence::<T>(&mut config, &mut provenance);
+        if let Err(e) = self.migrate(&mut config) {
+            report.warnings.push(e.to_string());
+        }
+        self.fill_defaults::<T>(&mut config, &mut provenance);
+        let missing = T::find_missing_required_fields(&config);
+
+        let check = self
+            .resolve::<T>(&mut config, &mut provenance)
+            .and_then(|redacted| {
+                self.check::<T>(&mut config, &provenance)
+                    .map_err(|e| Self::redact(e, &redacted))
+            });
+        match check {
+            Ok(()) => {}
+            Err(Error::Multiple(errors)) => report
+                .warnings
+                .extend(errors.iter().map(ToString::to_string)),
+            Err(e) => report.warnings.push(e.to_string()),
+        }
+        self.redact_paths(&mut config, &provenance);
+
+        for field in T::config_metadata() {
+            if field.skip || field.nested {
+                continue;
+            }
+
+            let value = T::get_nested_value(&config, &field.path).cloned();
+            let source = provenance.source(&field.path);
+            let warnings = if missing.contains(&field.path) {
+                vec!["missing required value".to_string()]
+            } else {
+                Vec::new()
+            };
+
+            report.fields.push(FieldReport {
+                default: source == Some(Source::Default) || (value.is_none() && field.has_default),
+                overridden: provenance.overridden(&field.path),
+                path: field.path,
+                value,
+                source,
+                warnings,
+            });
+        }
+
+        report
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigLoader;
+use crate::{
+    Error,
+    merge::{self, ConfigChange},
+};
+use serde::Serialize;
+
+impl ConfigLoader {
+    /// Lists the values of `config` that differ from `T::default()`
+    ///
+    /// Each [`ConfigChange`] holds the default as `old` and the loaded value as `new`.
+    /// Useful to see at a glance which settings of a deployment were changed.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use serde_json::json;
+    ///
+    /// #[derive(serde::Deserialize, serde::Serialize, Konfik, Default)]
+    /// struct AppConfig {
+    ///     host: String,
+    ///     port: u16,
+    ///     debug: bool,
+    /// }
+    ///
+    /// let config = AppConfig { host: String::new(), port: 8080, debug: true };
+    /// let drift = ConfigLoader::drift_from_defaults(&config)?;
+    ///
+    /// let paths = drift.iter().map(|change| change.path.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(paths, ["debug", "port"]);
+    /// assert_eq!(drift[1].old, Some(json!(0)));
+    /// assert_eq!(drift[1].new, Some(json!(8080)));
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if `config` or `T::default()` can't be serialized.
+    pub fn drift_from_defaults<T: Default + Serialize>(
+        config: &T,
+    ) -> Result<Vec<ConfigChange>, Error> {
+        let defaults = serde_json::to_value(T::default())?;
+        let config = serde_json::to_value(config)?;
+        Ok(merge::diff(&defaults, &config))
+    }
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, FileFormat, provenance::Provenance};
+use crate::{Error, config_meta::ConfigMeta};
+use serde::{Serialize, Serializer, de::DeserializeOwned};
+use serde_json::{Number, Value};
+
+impl ConfigLoader {
+    /// Serializes a merged config, e.g. from [`load_merged`](Self::load_merged), to `format`
+    ///
+    /// Floats are written as they are unless a precision is set with
+    /// [`with_float_precision`](Self::with_float_precision).
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, FileFormat};
+    /// use serde_json::json;
+    ///
+    /// let config = json!({ "name": "konfik", "ratio": 0.5 });
+    /// let dump = ConfigLoader::default().dump(&config, FileFormat::Toml)?;
+    ///
+    /// assert_eq!(dump, "name = \"konfik\"\nratio = 0.5\n");
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if `config` can't be represented in `format`, e.g. `null` values in TOML.
+    pub fn dump(&self, config: &Value, format: FileFormat) -> Result<String, Error> {
+        let mut config = config.clone();
+        if let Some(precision) = self.float_precision {
+            round_floats(&mut config, precision);
+        }
+
+        Ok(match format {
+            FileFormat::Json => serde_json::to_string_pretty(&config)? + "\n",
+            FileFormat::Toml => toml::to_string_pretty(&Plain(&config))?,
+            FileFormat::Yaml => Self::serialize_yaml(&Plain(&config))?,
+        })
+    }
+
+    /// Load the configuration like [`load`](Self::load), and dump the merged values it was
+    /// deserialized from to `format`
+    ///
+    /// The dump shows the effective config after merging all sources and normalizing the
+    /// values, ready to be logged or written to disk. Fields left to `#[serde(default)]`
+    /// don't appear in it. Values at [redacted paths](Self::with_redacted_paths) and
+    /// resolved [secrets](Self::with_secret_resolver) are replaced with `[REDACTED]`.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, FileFormat, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     password: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_load_and_dump.toml");
+    /// std::fs::write(&path, "port = 8080\npassword = \"hunter2\"")?;
+    ///
+    /// let (config, dump) = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .with_redacted_paths(&["password"])
+    ///     .load_and_dump::<AppConfig>(FileFormat::Toml)?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(dump, "password = \"[REDACTED]\"\nport = 8080\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load) and [`dump`](Self::dump).
+    pub fn load_and_dump<T>(&self, format: FileFormat) -> Result<(T, String), Error>
+    where
+        T: DeserializeOwned + ConfigMeta,
+    {
+        let mut provenance = Provenance::default();
+        let config = self.load_sources::<T>(&mut provenance)?;
+
+        #[cfg(feature = "prompt")]
+        let config = self.merge_prompts::<T>(config, &mut provenance)?;
+
+        let (config, mut raw) = self
+            .finish_with_raw::<T>(config, &mut provenance)?
+            .into_parts();
+        self.redact_paths(&mut raw, &provenance);
+        let dump = self.dump(&raw, format)?;
+        Ok((config, dump))
+    }
+}
+
+/// Rounds every float in `value` to `precision` decimal places.
+fn round_floats(value: &mut Value, precision: usize) {
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            if let Some(rounded) = n
+                .as_f64()
+                .and_then(|f| format!("{f:.precision$}").parse().ok())
+                .and_then(Number::from_f64)
+            {
+                *n = rounded;
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| round_floats(item, precision)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| round_floats(item, precision)),
+        _ => {}
+    }
+}
+
+/// Serializes numbers as plain `i64`, `u64` or `f64`.
+///
+/// With `serde_json`'s `arbitrary_precision` feature numbers serialize as a private
+/// struct that only `serde_json` understands.
+struct Plain<'a>(&'a Value);
+
+impl Serialize for Plain<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    serializer.serialize_i64(i)
+                } else if let Some(u) = n.as_u64() {
+                    serializer.serialize_u64(u)
+                } else {
+                    serializer.serialize_f64(n.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::Array(items) => serializer.collect_seq(items.iter().map(Plain)),
+            Value::Object(map) => {
+                serializer.collect_map(map.iter().map(|(key, value)| (key, Plain(value))))
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
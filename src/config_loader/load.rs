@@ -1,10 +1,20 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 kingananas20
 
-use super::ConfigLoader;
-use crate::{Error, config_meta::ConfigMeta};
-use clap::Parser;
+use super::{ConfigLoader, normalize, provenance::Provenance};
+use crate::{
+    Error, FileFormat, LoadedConfig, Priority, Source, ValidationMode,
+    config_meta::ConfigMeta,
+    merge::{self, Change},
+};
+use clap::{ArgMatches, Parser};
 use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 impl ConfigLoader {
     /// Load the configuration, from the specified sources but without CLI args, of type `T`.
@@ -21,31 +31,63 @@ impl ConfigLoader {
     where
         T: DeserializeOwned + ConfigMeta,
     {
-        let mut config = serde_json::Value::Object(serde_json::Map::new());
+        let mut provenance = Provenance::default();
+        let config = self.load_sources::<T>(&mut provenance)?;
 
-        // 1. Load from config files (lowest priority)
-        for file_path in &self.config_files {
-            if let Some(file_config) = Self::load_file(file_path)? {
-                config = Self::merge_json(config, file_config);
-            }
-        }
+        #[cfg(feature = "prompt")]
+        let config = self.merge_prompts::<T>(config, &mut provenance)?;
 
-        // 2. Load from environment (medium priority)
-        if self.env_prefix.is_some() {
-            let env_config = self.load_env::<T>();
-            config = Self::merge_json(config, env_config);
-        }
+        self.finish(config, &mut provenance)
+    }
 
-        // 4. Validate
-        if let Some(validator) = &self.validation {
-            validator(&config)?;
-        }
+    /// Load the configuration of type `T` from env vars only
+    ///
+    /// Config files, layers, custom sources and prompts are skipped, so no file is checked
+    /// for existence. Otherwise it behaves exactly like [`load`](Self::load) without any
+    /// files: prefixed env vars, globs, the override var and systemd credentials are
+    /// merged, then defaults, transforms and validators apply. Meant for small configs
+    /// loaded on a hot path, e.g. per invocation of a serverless function.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     region: String,
+    ///     #[serde(default)]
+    ///     timeout: u32,
+    /// }
+    ///
+    /// // SAFETY: nothing else touches the environment in this example
+    /// unsafe { std::env::set_var("FAST_REGION", "eu-west-1") };
+    ///
+    /// let path = std::env::temp_dir().join("konfik_load_env_fast.toml");
+    /// std::fs::write(&path, "timeout = 30")?;
+    ///
+    /// let config = ConfigLoader::default()
+    ///     .with_env_prefix("FAST")
+    ///     .with_config_file(&path)
+    ///     .load_env_fast::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.region, "eu-west-1");
+    /// assert_eq!(config.timeout, 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load), except for errors of reading files.
+    pub fn load_env_fast<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned + ConfigMeta,
+    {
+        let mut provenance = Provenance::default();
+        let mut config = self
+            .merge_env::<T>(Value::Object(Map::new()), &mut provenance)
+            .map_err(|e| Self::redact_sources(e, &provenance))?;
+        self.rename_deprecated::<T>(&mut config);
 
-        // 5. Deserialize
-        serde_json::from_value::<T>(config).map_err(|e| Error::ConfigParse {
-            type_name: std::any::type_name::<T>(),
-            source: e,
-        })
+        self.finish(config, &mut provenance)
     }
 
     /// Load the configuration, from the specified sources with CLI args, of type `T`.
@@ -62,54 +104,970 @@ impl ConfigLoader {
     where
         T: DeserializeOwned + ConfigMeta + Parser,
     {
-        let mut config = serde_json::Value::Object(serde_json::Map::new());
+        self.load_with_matches().map(|(config, _)| config)
+    }
 
-        // 1. Load from config files (lowest priority)
-        for file_path in &self.config_files {
-            if let Some(file_config) = Self::load_file(file_path)? {
-                config = Self::merge_json(config, file_config);
-            }
+    /// Load the configuration like [`load_with_cli`](Self::load_with_cli), and return the
+    /// parsed CLI args next to it
+    ///
+    /// Apps handling some flags themselves, like verbosity, can read them from the matches
+    /// instead of parsing the args a second time with a conflicting definition. Fields only
+    /// meant for the CLI can be marked `#[serde(skip)]`, their args are still parsed.
+    ///
+    /// ```rust
+    /// use clap::ArgAction;
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik, clap::Parser)]
+    /// struct AppConfig {
+    ///     #[arg(long)]
+    ///     port: u16,
+    ///     #[serde(skip)]
+    ///     #[arg(short, action = ArgAction::Count)]
+    ///     verbose: u8,
+    /// }
+    ///
+    /// let (config, matches) = ConfigLoader::default()
+    ///     .with_cli_args(["myapp", "--port", "8080", "-vv"])
+    ///     .load_with_matches::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(matches.get_count("verbose"), 2);
+    /// # Ok::<(), konfik::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load_with_cli`](Self::load_with_cli).
+    pub fn load_with_matches<T>(&self) -> Result<(T, ArgMatches), Error>
+    where
+        T: DeserializeOwned + ConfigMeta + Parser,
+    {
+        let mut provenance = Provenance::default();
+        let mut config = self.load_sources::<T>(&mut provenance)?;
+
+        // 3. Load from CLI args (highest priority)
+        let (cli_config, matches) = self
+            .load_cli::<T>(&config, &mut provenance)
+            .map_err(|e| Self::redact_sources(e, &provenance))?;
+        config = self.merge_json(config, cli_config, Source::Cli, &mut provenance);
+
+        #[cfg(feature = "prompt")]
+        let config = self.merge_prompts::<T>(config, &mut provenance)?;
+
+        self.finish(config, &mut provenance)
+            .map(|config| (config, matches))
+    }
+
+    /// Load the configuration like [`load`](Self::load), but keep the merged value next to `T`
+    ///
+    /// The merged value also holds keys that `T` has no field for, e.g. free form
+    /// extension settings, which can be read with [`LoadedConfig::get_raw`].
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_load_with_raw.toml");
+    /// std::fs::write(&path, "port = 8080\n[extensions.metrics]\nenabled = true")?;
+    ///
+    /// let loaded = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .load_with_raw::<AppConfig>()?;
+    ///
+    /// assert_eq!(loaded.port, 8080);
+    /// assert_eq!(loaded.get_raw("extensions.metrics.enabled"), Some(&serde_json::json!(true)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load).
+    pub fn load_with_raw<T>(&self) -> Result<LoadedConfig<T>, Error>
+    where
+        T: DeserializeOwned + ConfigMeta,
+    {
+        let mut provenance = Provenance::default();
+        let config = self.load_sources::<T>(&mut provenance)?;
+
+        #[cfg(feature = "prompt")]
+        let config = self.merge_prompts::<T>(config, &mut provenance)?;
+
+        self.finish_with_raw(config, &mut provenance)
+    }
+
+    /// Load the configuration like [`load`](Self::load), and return the keys `T` has no field for
+    ///
+    /// The extras are keyed by their dotted path, e.g. `database.pool_size` for an unknown key
+    /// inside a known nested table. Config files written for a newer version of the app keep
+    /// loading, without their new keys being silently dropped.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use serde_json::json;
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_load_with_extras.toml");
+    /// std::fs::write(&path, "port = 8080\ntimeout = 30\n[tracing]\nenabled = true")?;
+    ///
+    /// let (config, extras) = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .load_with_extras::<AppConfig>()?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(extras["timeout"], json!(30));
+    /// assert_eq!(extras["tracing"], json!({ "enabled": true }));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load).
+    pub fn load_with_extras<T>(&self) -> Result<(T, Map<String, Value>), Error>
+    where
+        T: DeserializeOwned + ConfigMeta,
+    {
+        let (config, raw) = self.load_with_raw::<T>()?.into_parts();
+
+        let extras = T::find_unknown_fields(&raw)
+            .into_iter()
+            .filter_map(|path| {
+                let value = T::get_nested_value(&raw, &path)?.clone();
+                Some((path, value))
+            })
+            .collect();
+
+        Ok((config, extras))
+    }
+
+    /// Loads the configuration again like [`load`](Self::load), but keeps the values of
+    /// the `#[serde(skip)]` fields of `previous`
+    ///
+    /// Skipped fields are populated by the app after loading, e.g. with connections or
+    /// caches. A plain reload would reset them to their defaults, this one only replaces
+    /// the fields that come from the config.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    ///
+    /// #[derive(Clone, serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    ///     #[serde(skip)]
+    ///     connected: bool,
+    /// }
+    ///
+    /// #[derive(Clone, serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    ///     #[serde(skip)]
+    ///     requests: u64,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_reload.toml");
+    /// std::fs::write(&path, "port = 80\n[database]\nurl = \"postgres://old\"")?;
+    ///
+    /// let loader = ConfigLoader::default().with_config_file(&path);
+    /// let mut config = loader.load::<AppConfig>()?;
+    /// config.requests = 42;
+    /// config.database.connected = true;
+    ///
+    /// std::fs::write(&path, "port = 8080\n[database]\nurl = \"postgres://new\"")?;
+    /// let config = loader.reload(&config)?;
+    ///
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(config.database.url, "postgres://new");
+    /// assert_eq!(config.requests, 42);
+    /// assert!(config.database.connected);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load).
+    pub fn reload<T>(&self, previous: &T) -> Result<T, Error>
+    where
+        T: DeserializeOwned + ConfigMeta + Clone,
+    {
+        let mut config = self.load::<T>()?;
+        config.carry_skipped(&mut previous.clone());
+        Ok(config)
+    }
+
+    /// Merges and validates the configuration from the specified sources, without CLI args,
+    /// but stops before deserializing it.
+    ///
+    /// The returned value can be shared between subsystems, which then deserialize their
+    /// own slices with [`deserialize_at`](crate::deserialize_at) instead of loading the
+    /// sources again.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct CoreConfig {
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct CachePlugin {
+    ///     size: u32,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_load_merged.toml");
+    /// std::fs::write(&path, "name = \"core\"\n[plugins.cache]\nsize = 64")?;
+    ///
+    /// let merged = ConfigLoader::default()
+    ///     .with_config_file(&path)
+    ///     .load_merged::<CoreConfig>()?;
+    ///
+    /// let core = konfik::deserialize_at::<CoreConfig>(&merged, "")?;
+    /// let cache = konfik::deserialize_at::<CachePlugin>(&merged, "plugins.cache")?;
+    ///
+    /// assert_eq!(core.name, "core");
+    /// assert_eq!(cache.size, 64);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load`](Self::load), except for deserialization errors
+    /// and errors of post load hooks.
+    pub fn load_merged<T: ConfigMeta>(&self) -> Result<Value, Error> {
+        let mut provenance = Provenance::default();
+        let mut config = self.load_sources::<T>(&mut provenance)?;
+
+        Self::apply_precedence::<T>(&mut config, &mut provenance);
+        self.migrate(&mut config)?;
+        self.check_explicit::<T>(&config)?;
+        self.fill_defaults::<T>(&mut config, &mut provenance);
+        let redacted = self.resolve::<T>(&mut config, &mut provenance)?;
+        self.check::<T>(&mut config, &provenance)
+            .map_err(|e| Self::redact(e, &redacted))?;
+        Ok(config)
+    }
+
+    /// Checks that every required field of `T` can be provided by files, env vars or defaults
+    ///
+    /// A quick readiness check for orchestrators. The existing config files are parsed,
+    /// and a required field is provided when one of them sets it, it has a default, or one
+    /// of its env vars is set. Custom sources, remote URLs, the config location and the
+    /// Cargo manifest table aren't called, since they can be slow, and count as providing
+    /// every field once configured. CLI args aren't considered.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Error, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     database_url: String,
+    ///     api_key: String,
+    ///     #[serde(default)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let result = ConfigLoader::default()
+    ///     .with_env_prefix("PREFLIGHT")
+    ///     .with_env_vars([("PREFLIGHT_DATABASE_URL", "postgres://db")])
+    ///     .with_config_file("missing.toml")
+    ///     .preflight::<AppConfig>();
+    ///
+    /// let Err(Error::MissingRequired(missing)) = result else { panic!("expected missing fields") };
+    /// assert_eq!(missing, ["api_key"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingRequired`] with the paths of all missing fields, or the
+    /// errors of finding, reading and parsing the config files.
+    pub fn preflight<T: ConfigMeta>(&self) -> Result<(), Error> {
+        let mut provenance = Provenance::default();
+        let mut config = Value::Object(Map::new());
+        for (_, path, format) in self.files_in_merge_order()? {
+            config = self
+                .merge_file::<T>(config, &path, format, &mut provenance)
+                .map_err(|e| Self::redact_sources(e, &provenance))?;
         }
+        self.rename_deprecated::<T>(&mut config);
+        self.fill_defaults::<T>(&mut config, &mut provenance);
+
+        let metadata = T::config_metadata();
+        let mut missing = T::find_missing_required_fields(&config)
+            .into_iter()
+            .filter(|path| {
+                !metadata
+                    .iter()
+                    .find(|field| field.path == *path)
+                    .is_some_and(|field| self.env_provides(field))
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() || self.has_unread_sources() {
+            return Ok(());
+        }
+
+        missing.sort();
+        Err(Error::MissingRequired(missing))
+    }
+
+    /// If a source that preflight doesn't read is configured.
+    fn has_unread_sources(&self) -> bool {
+        !self.sources.is_empty()
+            || !self.remote_urls.is_empty()
+            || self.cargo_metadata.is_some()
+            || self.config_location().is_some()
+    }
+
+    /// Merges config files and environment variables.
+    ///
+    /// Errors are scrubbed of the redacted values of every source merged before them.
+    pub(super) fn load_sources<T: ConfigMeta>(
+        &self,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        self.load_sources_with_update::<T>(None, provenance)
+    }
+
+    /// Like [`load_sources`](Self::load_sources), with the update of a
+    /// [`ConfigStream`](crate::config_source::ConfigStream) merged between the config files
+    /// and the environment.
+    pub(super) fn load_sources_with_update<T: ConfigMeta>(
+        &self,
+        update: Option<Value>,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        self.merge_all_sources::<T>(update, provenance)
+            .map_err(|e| Self::redact_sources(e, provenance))
+    }
+
+    fn merge_all_sources<T: ConfigMeta>(
+        &self,
+        update: Option<Value>,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        let config = Value::Object(serde_json::Map::new());
+
+        // 1. Load from config files and custom sources (lowest priority)
+        let config = self.merge_files::<T>(config, update, provenance)?;
 
         // 2. Load from environment (medium priority)
+        let mut config = self.merge_env::<T>(config, provenance)?;
+
+        self.rename_deprecated::<T>(&mut config);
+        Ok(config)
+    }
+
+    /// Merges all config files and custom sources into `config`, by their [`Priority`].
+    ///
+    /// Within a priority, files are merged before sources. A stream `update` is merged on
+    /// top of the [`Priority::Normal`] files and sources.
+    fn merge_files<T: ConfigMeta>(
+        &self,
+        mut config: Value,
+        update: Option<Value>,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        let files = self.files_in_merge_order()?;
+        let merge_files = |config, priority, provenance: &mut Provenance| {
+            files
+                .iter()
+                .filter(|(file_priority, _, _)| *file_priority == priority)
+                .try_fold(config, |config, (_, file_path, format)| {
+                    self.merge_file::<T>(config, file_path, *format, provenance)
+                })
+        };
+
+        config = merge_files(config, Priority::Low, provenance)?;
+        config = self.merge_sources::<T>(config, Priority::Low, provenance)?;
+        config = merge_files(config, Priority::Normal, provenance)?;
+
+        for url in &self.remote_urls {
+            let remote_config = self.fetch(url)?;
+            let remote_config = self.file_layer::<T>(remote_config, Path::new(url))?;
+            config = self.merge_json(config, remote_config, Source::Remote, provenance);
+        }
+
+        if let Some(tool) = &self.cargo_metadata {
+            let manifests = Self::ancestor_config_files(Path::new("Cargo.toml"), true)?;
+            if let Some(manifest) = manifests.first()
+                && let Some(metadata) = Self::load_file(manifest, None)?
+                && let Some(tool_config) =
+                    T::get_nested_value(&metadata, &format!("package.metadata.{tool}")).cloned()
+            {
+                let tool_config = self.file_layer::<T>(tool_config, manifest)?;
+                config = self.merge_json(config, tool_config, Source::File, provenance);
+            }
+        }
+
+        if let Some(location) = self.config_location() {
+            let location_config = self.load_location(&location)?;
+            let location_config = self.file_layer::<T>(location_config, Path::new(&location))?;
+            config = self.merge_json(config, location_config, Source::File, provenance);
+        }
+
+        config = self.merge_sources::<T>(config, Priority::Normal, provenance)?;
+        if let Some(update) = update {
+            config = self.merge_json(config, update, Source::Remote, provenance);
+        }
+
+        config = merge_files(config, Priority::High, provenance)?;
+        self.merge_sources::<T>(config, Priority::High, provenance)
+    }
+
+    /// Every config file in merge order without duplicates, next to its [`Priority`] and
+    /// the format it was registered with.
+    fn files_in_merge_order(&self) -> Result<Vec<(Priority, PathBuf, Option<FileFormat>)>, Error> {
+        // Keyed by canonical path, so a format survives the file being listed as `./app.conf`
+        // and `app.conf`
+        let formats = self
+            .file_formats
+            .iter()
+            .map(|(path, format)| (canonical(path), *format))
+            .collect::<HashMap<_, _>>();
+
+        let files = self.files_by_priority()?;
+        Ok(
+            Self::dedup_files(files.iter().map(|(priority, path)| (*priority, path)))
+                .into_iter()
+                .map(|(priority, canonical, path)| {
+                    (priority, path.clone(), formats.get(&canonical).copied())
+                })
+                .collect(),
+        )
+    }
+
+    /// Every config file in merge order, next to its [`Priority`].
+    fn files_by_priority(&self) -> Result<Vec<(Priority, PathBuf)>, Error> {
+        let mut dir_files = Vec::new();
+        let env_dirs = self
+            .config_dir_env_vars
+            .iter()
+            .filter_map(|var| self.var_os(var))
+            .map(PathBuf::from);
+        for dir in self.layered_dirs.iter().cloned().chain(env_dirs) {
+            dir_files.extend(Self::dir_config_files(&dir)?);
+        }
+
+        let env_files = self
+            .config_file_env_vars
+            .iter()
+            .filter_map(|var| self.var_os(var))
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+
+        let first_match_files = self
+            .first_match_files
+            .iter()
+            .filter_map(|candidates| candidates.iter().find(|path| path.exists()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut ancestor_files = Vec::new();
+        for (filename, nearest_only) in &self.ancestor_files {
+            ancestor_files.extend(Self::ancestor_config_files(filename, *nearest_only)?);
+        }
+
+        let config_files = |priority| {
+            self.config_files
+                .iter()
+                .filter(move |(_, file_priority, _)| *file_priority == priority)
+                .map(|(path, _, prio)| (path, *prio))
+        };
+
+        let mut low = config_files(Priority::Low).collect::<Vec<_>>();
+        // Files found in directories or by name have the default numeric priority
+        let mut normal = dir_files
+            .iter()
+            .map(|path| (path, 0))
+            .chain(config_files(Priority::Normal))
+            .chain(first_match_files.iter().map(|path| (path, 0)))
+            .chain(ancestor_files.iter().map(|path| (path, 0)))
+            .collect::<Vec<_>>();
+        let mut high = config_files(Priority::High).collect::<Vec<_>>();
+        // Stable, so files with the same numeric priority keep the order they were added in
+        for files in [&mut low, &mut normal, &mut high] {
+            files.sort_by_key(|(_, prio)| *prio);
+        }
+        // The file named by an env var comes last
+        high.extend(env_files.iter().map(|path| (path, 0)));
+
+        Ok([
+            (Priority::Low, low),
+            (Priority::Normal, normal),
+            (Priority::High, high),
+        ]
+        .into_iter()
+        .flat_map(|(priority, files)| {
+            files
+                .into_iter()
+                .map(move |(path, _)| (priority, path.clone()))
+        })
+        .collect())
+    }
+
+    /// Merges a single config file into `config`, if it exists.
+    fn merge_file<T: ConfigMeta>(
+        &self,
+        config: Value,
+        file_path: &Path,
+        format: Option<FileFormat>,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        let Some(file_config) = Self::load_file(file_path, format)? else {
+            return Ok(config);
+        };
+
+        let file_config = self.file_layer::<T>(file_config, file_path)?;
+        Ok(self.merge_json(config, file_config, Source::File, provenance))
+    }
+
+    /// Applies the namespace, profiles and key case of the loader to the parsed document
+    /// at `path`, and runs the per file validators on the result.
+    fn file_layer<T: ConfigMeta>(&self, file_config: Value, path: &Path) -> Result<Value, Error> {
+        let file_config = self.namespaced(file_config, &path.display())?;
+        let file_config = self.profiled(file_config);
+        let file_config = self.match_case::<T>(file_config)?;
+        self.validate_file(path, &file_config)?;
+        Ok(file_config)
+    }
+
+    /// Merges the custom sources of `priority` into `config`, in the order they were added.
+    fn merge_sources<T: ConfigMeta>(
+        &self,
+        mut config: Value,
+        priority: Priority,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        let mut sources = self
+            .sources
+            .iter()
+            .filter(|(_, source_priority)| *source_priority == priority)
+            .peekable();
+        if sources.peek().is_none() {
+            return Ok(config);
+        }
+
+        let fields = T::config_metadata();
+        for (source, _) in sources {
+            let source_config = source.load(&fields)?;
+            config = self.merge_json(config, source_config, Source::Custom, provenance);
+        }
+
+        Ok(config)
+    }
+
+    /// Removes files that are listed more than once, keeping their last position, and
+    /// returns them next to their key and canonical path.
+    ///
+    /// Existing files are compared by their canonical path, so `./config.toml` and
+    /// `config.toml` are the same file. Merging a file again at its last position gives
+    /// the same result as merging it at every position.
+    fn dedup_files<'a, K>(
+        files: impl Iterator<Item = (K, &'a PathBuf)>,
+    ) -> Vec<(K, PathBuf, &'a PathBuf)> {
+        let mut files = files
+            .map(|(key, path)| (key, canonical(path), path))
+            .collect::<Vec<_>>();
+
+        let mut seen = HashSet::new();
+        files.reverse();
+        files.retain(|(_, canonical, _)| seen.insert(canonical.clone()));
+        files.reverse();
+
+        files
+    }
+
+    /// Runs the per file validators against the config of a single file.
+    fn validate_file(&self, path: &Path, file_config: &Value) -> Result<(), Error> {
+        for validator in &self.file_validation {
+            validator(path, file_config).map_err(|e| {
+                let mut redacted = Vec::new();
+                self.collect_redacted(file_config, &mut redacted);
+                Error::InvalidFile {
+                    path: path.to_path_buf(),
+                    source: Box::new(Self::redact(e, &redacted)),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Extracts the namespace of this loader from the config of a single file.
+    fn namespaced(&self, file_config: Value, file: &dyn Display) -> Result<Value, Error> {
+        let Some(namespace) = &self.namespace else {
+            return Ok(file_config);
+        };
+
+        match file_config {
+            Value::Object(mut map) if map.get(namespace).is_some_and(Value::is_object) => {
+                Ok(map.remove(namespace).unwrap_or_default())
+            }
+            _ if self.namespace_required => Err(Error::Validation(format!(
+                "{file} has no `{namespace}` section"
+            ))),
+            file_config => Ok(file_config),
+        }
+    }
+
+    /// Merges the profile sections of a single file in order.
+    fn profiled(&self, file_config: Value) -> Value {
+        let Value::Object(map) = &file_config else {
+            return file_config;
+        };
+
+        let sections = self
+            .profiles
+            .iter()
+            .filter_map(|profile| map.get(profile).filter(|section| section.is_object()))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if sections.is_empty() {
+            return file_config;
+        }
+        sections
+            .into_iter()
+            .fold(Value::Object(serde_json::Map::new()), merge::merge)
+    }
+
+    /// Merges the environment variables and systemd credentials into `config`.
+    fn merge_env<T: ConfigMeta>(
+        &self,
+        mut config: Value,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
         if self.env_prefix.is_some() {
-            let env_config = self.load_env::<T>();
-            config = Self::merge_json(config, env_config);
+            let env_config = self.load_env::<T>()?;
+            config = self.merge_json(config, env_config, Source::Env, provenance);
+        }
+
+        if let Some(globs) = self.load_env_globs()? {
+            config = self.merge_json(config, globs, Source::Env, provenance);
         }
 
-        let cli_config = Self::load_cli::<T>(&config);
-        config = Self::merge_json(config, cli_config);
+        if let Some(overrides) = self.load_env_override()? {
+            config = self.merge_json(config, overrides, Source::Env, provenance);
+        }
 
-        // 4. Validate
-        if let Some(validator) = &self.validation {
-            validator(&config)?;
+        if let Some(credentials) = self.load_credentials::<T>()? {
+            config = self.merge_json(config, credentials, Source::Credential, provenance);
         }
 
+        Ok(config)
+    }
+
+    /// Validates the merged config and deserializes it into `T`.
+    pub(super) fn finish<T: DeserializeOwned + ConfigMeta>(
+        &self,
+        config: Value,
+        provenance: &mut Provenance,
+    ) -> Result<T, Error> {
+        self.finish_with_raw(config, provenance)
+            .map(LoadedConfig::into_inner)
+    }
+
+    /// Like [`finish`](Self::finish), but keeps the validated value.
+    pub(super) fn finish_with_raw<T: DeserializeOwned + ConfigMeta>(
+        &self,
+        mut config: Value,
+        provenance: &mut Provenance,
+    ) -> Result<LoadedConfig<T>, Error> {
+        Self::apply_precedence::<T>(&mut config, provenance);
+        self.migrate(&mut config)?;
+        self.check_explicit::<T>(&config)?;
+        self.fill_defaults::<T>(&mut config, provenance);
+        let redacted = self.resolve::<T>(&mut config, provenance)?;
+        self.deserialize::<T>(&mut config, provenance)
+            .map(|typed| LoadedConfig::new(typed, config))
+            .map_err(|e| Self::redact(e, &redacted))
+    }
+
+    fn deserialize<T: DeserializeOwned + ConfigMeta>(
+        &self,
+        config: &mut Value,
+        provenance: &Provenance,
+    ) -> Result<T, Error> {
+        // 4. Normalize and validate
+        self.check::<T>(config, provenance)?;
+
         // 5. Deserialize
-        serde_json::from_value::<T>(config).map_err(|e| Error::ConfigParse {
-            type_name: std::any::type_name::<T>(),
-            source: e,
-        })
+        let config = serde_path_to_error::deserialize(&*config).map_err(|e| {
+            let path = dotted_path(e.path());
+            let source = if self.is_redacted(&path, provenance) {
+                // The message can echo the value, whatever type it has
+                serde::de::Error::custom(format!("invalid value at {path}: [REDACTED]"))
+            } else {
+                e.into_inner()
+            };
+            Error::ConfigParse {
+                type_name: std::any::type_name::<T>(),
+                source,
+            }
+        })?;
+
+        // 6. Post load hooks
+        self.run_post_load(&config)?;
+
+        Ok(config)
     }
 
-    fn merge_json(base: serde_json::Value, overlay: serde_json::Value) -> serde_json::Value {
-        use serde_json::Value;
+    /// Runs the post load hooks accepting the type of `config`, hooks of other types are skipped.
+    fn run_post_load<T: ConfigMeta>(&self, config: &T) -> Result<(), Error> {
+        // Hooks can't be added for configs without `as_any`
+        let Some(config) = config.as_any() else {
+            return Ok(());
+        };
 
-        match (base, overlay) {
-            (Value::Object(mut base_map), Value::Object(overlay_map)) => {
-                for (key, value) in overlay_map {
-                    match base_map.get(&key) {
-                        Some(base_value) if base_value.is_object() && value.is_object() => {
-                            base_map.insert(key, Self::merge_json(base_value.clone(), value));
-                        }
-                        _ => {
-                            base_map.insert(key, value);
-                        }
-                    }
+        let type_id = config.type_id();
+        self.post_load
+            .iter()
+            .filter(|(hook_type, _)| *hook_type == type_id)
+            .try_for_each(|(_, hook)| hook(config))
+    }
+
+    /// The environment selecting the environment specific validators.
+    fn active_environment(&self) -> Option<String> {
+        self.environment_var
+            .as_ref()
+            .and_then(|var| self.var_os(var)?.into_string().ok())
+            .or_else(|| self.profiles.last().cloned())
+    }
+
+    /// Sets fields with a `precedence` source to the value of that source, if it has one.
+    pub(super) fn apply_precedence<T: ConfigMeta>(config: &mut Value, provenance: &mut Provenance) {
+        for field in T::config_metadata() {
+            let Some(source) = field.precedence else {
+                continue;
+            };
+            let Some(value) = provenance
+                .layer(source)
+                .and_then(|layer| T::get_nested_value(layer, &field.path))
+                .cloned()
+            else {
+                continue;
+            };
+
+            provenance.record(&field.path, &value, source);
+            *config = merge::merge(config.take(), merge::at_path(&field.path, value));
+        }
+    }
+
+    /// Fails if explicit values are required and a field of `T` isn't set by any source.
+    fn check_explicit<T: ConfigMeta>(&self, config: &Value) -> Result<(), Error> {
+        if !self.require_explicit {
+            return Ok(());
+        }
+
+        let implicit = T::config_metadata()
+            .into_iter()
+            .filter(|field| !field.skip && !field.nested)
+            .filter(|field| T::get_nested_value(config, &field.path).is_none())
+            .map(|field| field.path)
+            .collect::<Vec<_>>();
+        if implicit.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::Validation(format!(
+            "fields without an explicit value: {}",
+            implicit.join(", ")
+        )))
+    }
+
+    /// Inserts the fill defaults, the `default_if_none` values and the results of the default
+    /// functions of `T` at every path that is still absent.
+    pub(super) fn fill_defaults<T: ConfigMeta>(
+        &self,
+        config: &mut Value,
+        provenance: &mut Provenance,
+    ) {
+        fn fill(config: &mut Value, defaults: &Value, path: &str, provenance: &mut Provenance) {
+            let (Value::Object(map), Value::Object(defaults)) = (config, defaults) else {
+                return;
+            };
+
+            for (key, default) in defaults {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+
+                if let Some(existing) = map.get_mut(key) {
+                    fill(existing, default, &key_path, provenance);
+                } else {
+                    provenance.record(&key_path, default, Source::Default);
+                    map.insert(key.clone(), default.clone());
                 }
-                Value::Object(base_map)
             }
-            (_, overlay) => overlay,
+        }
+
+        if let Some(defaults) = &self.fill_defaults {
+            fill(config, defaults, "", provenance);
+        }
+
+        for field in T::config_metadata() {
+            let existing = T::get_nested_value(config, &field.path);
+            let default = if let Some(default) = field.default_if_none {
+                let absent = match existing {
+                    None => true,
+                    Some(Value::Null) => field.null_uses_default,
+                    Some(_) => false,
+                };
+                absent.then(|| normalize::parse_for_type(default, &field.ty))
+            } else if let Some(default_fn) = field.default_fn
+                && existing.is_none()
+            {
+                // serde still calls the function if its value can't be serialized
+                default_fn().ok()
+            } else {
+                None
+            };
+
+            if let Some(default) = default {
+                provenance.record(&field.path, &default, Source::Default);
+                *config = merge::merge(config.take(), merge::at_path(&field.path, default));
+            }
         }
     }
+
+    /// Resolves value references and secrets, and returns the values to scrub from errors.
+    ///
+    /// The values at redacted paths are taken from every source, including overridden
+    /// values, and collected from `config` before and after resolving, so neither a reference nor the
+    /// secret it resolves to shows up in error messages. Resolved secrets are redacted like
+    /// the values at redacted paths, their paths are recorded in `provenance`.
+    pub(super) fn resolve<T: ConfigMeta>(
+        &self,
+        config: &mut Value,
+        provenance: &mut Provenance,
+    ) -> Result<Vec<String>, Error> {
+        let mut redacted = provenance.redacted().to_vec();
+        self.collect_redacted(config, &mut redacted);
+
+        if self.value_references {
+            Self::resolve_references::<T>(config).map_err(|e| Self::redact(e, &redacted))?;
+        }
+        let secret_paths = self
+            .resolve_secrets(config)
+            .map_err(|e| Self::redact(e, &redacted))?;
+        provenance.add_secret_paths(secret_paths);
+
+        self.collect_redacted(config, &mut redacted);
+        Self::collect_secrets(config, provenance, &mut redacted);
+        Ok(redacted)
+    }
+
+    /// Normalizes the merged config and runs all validators on it.
+    pub(super) fn check<T: ConfigMeta>(
+        &self,
+        config: &mut Value,
+        provenance: &Provenance,
+    ) -> Result<(), Error> {
+        self.normalize::<T>(config, provenance)?;
+
+        if self.deny_unknown_fields || T::deny_unknown_fields() {
+            let unknown = T::find_unknown_fields(config);
+            if !unknown.is_empty() {
+                return Err(Error::Validation(format!(
+                    "unknown config keys: {}",
+                    unknown.join(", ")
+                )));
+            }
+        }
+
+        #[cfg(feature = "regex")]
+        self.validate_patterns::<T>(config, provenance)?;
+
+        for (source, validator) in &self.source_validation {
+            validator(&provenance.filter(config, *source))?;
+        }
+
+        if let Some(validator) = &self.validation {
+            validator(config)?;
+        }
+
+        if let Some(environment) = self.active_environment() {
+            for (_, validator) in self
+                .env_validation
+                .iter()
+                .filter(|(validator_environment, _)| *validator_environment == environment)
+            {
+                validator(config)?;
+            }
+        }
+
+        let mut failures = Vec::new();
+        for (mode, validator) in &self.mode_validation {
+            if let Err(e) = validator(config) {
+                failures.push(e);
+                if *mode == ValidationMode::FailFast {
+                    break;
+                }
+            }
+        }
+
+        if failures.len() > 1 {
+            return Err(Error::Multiple(failures));
+        }
+        failures.pop().map_or(Ok(()), Err)
+    }
+
+    pub(super) fn merge_json(
+        &self,
+        base: Value,
+        overlay: Value,
+        source: Source,
+        provenance: &mut Provenance,
+    ) -> Value {
+        provenance.add_layer(source, &overlay);
+        self.collect_redacted(&overlay, provenance.redacted_mut());
+
+        merge::merge_with(
+            base,
+            overlay,
+            self.array_merge,
+            self.delete_sentinel.as_ref(),
+            &mut |path, change| match change {
+                Change::Set { old, new } => {
+                    if let (Some(handler), Some(old)) = (&self.conflict_handler, old)
+                        && old != new
+                    {
+                        handler(path, old, new, source);
+                    }
+                    provenance.record(path, new, source);
+                }
+                Change::Appended(merged) => provenance.record(path, merged, source),
+                Change::Removed => provenance.remove(path),
+            },
+        )
+    }
+}
+
+/// The canonical form of `path`, or `path` itself if it doesn't exist.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The dotted path of a deserialization error, array items are addressed by their index.
+fn dotted_path(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            serde_path_to_error::Segment::Seq { index } => index.to_string(),
+            serde_path_to_error::Segment::Map { key } => key.clone(),
+            serde_path_to_error::Segment::Enum { variant } => variant.clone(),
+            serde_path_to_error::Segment::Unknown => "?".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
 }
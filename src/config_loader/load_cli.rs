@@ -1,42 +1,269 @@
 // SPDX-License-Identifier: MIT
 // Copyright (c) 2025 kingananas20
 
-use super::ConfigLoader;
-use crate::config_meta::ConfigMeta;
+use super::{
+    ConfigLoader,
+    print_config::{PRINT_CONFIG, PRINT_CONFIG_FORMAT},
+    provenance::Provenance,
+};
+use crate::{Error, Source, config_meta::ConfigMeta, merge};
 use clap::ArgMatches;
 use serde_json::{Map, Value};
-use std::ffi::OsString;
+use std::{
+    collections::{HashMap, HashSet},
+    ffi::OsString,
+};
+
+/// An arg group of the CLI
+struct CliGroup {
+    id: String,
+    args: Vec<String>,
+    required: bool,
+    exclusive: bool,
+}
+
+impl CliGroup {
+    fn from_command(cmd: &clap::Command) -> Vec<Self> {
+        cmd.get_groups()
+            .cloned()
+            .map(|mut group| Self {
+                id: group.get_id().to_string(),
+                args: group.get_args().map(ToString::to_string).collect(),
+                required: group.is_required_set(),
+                exclusive: !group.is_multiple(),
+            })
+            .collect()
+    }
+
+    /// The member chosen on the CLI replaces the other members of exclusive groups.
+    fn resolve_exclusive(&self, cli: &mut Map<String, Value>) {
+        if !self.exclusive {
+            return;
+        }
+
+        if let Some(chosen) = self.args.iter().find(|arg| cli.contains_key(*arg)) {
+            for other in self.args.iter().filter(|arg| *arg != chosen) {
+                cli.insert(other.clone(), Value::Null);
+            }
+        }
+    }
+
+    /// Required groups need a member from any source.
+    fn check_required(&self, merged: &Value) -> Result<(), Error> {
+        if self.required
+            && !self
+                .args
+                .iter()
+                .any(|arg| merged.get(arg).is_some_and(|value| !value.is_null()))
+        {
+            return Err(Error::Validation(format!(
+                "one of {} is required",
+                self.args.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+}
 
 impl ConfigLoader {
-    pub(super) fn load_cli<T: ConfigMeta + clap::Parser>(current_config: &Value) -> Value {
-        let missing_required = T::find_missing_required_fields(current_config);
+    /// Parses the CLI args into a value to merge, and returns the matches they came from.
+    ///
+    /// With `--print-config`, the config is printed with `provenance` of `current_config`
+    /// and the process exits.
+    pub(super) fn load_cli<T: ConfigMeta + clap::Parser>(
+        &self,
+        current_config: &Value,
+        provenance: &mut Provenance,
+    ) -> Result<(Value, ArgMatches), Error> {
+        let args = self
+            .cli_args
+            .clone()
+            .unwrap_or_else(|| std::env::args_os().collect());
+        // Printing the config must work while required fields are still missing
+        let print_config = self.print_config_requested(&args);
+
+        let missing_paths = if print_config {
+            HashSet::new()
+        } else {
+            T::find_missing_required_fields(current_config)
+        };
+        // Arg ids are field names, flattened fields are moved to their path afterwards
+        let missing_required = T::config_metadata()
+            .into_iter()
+            .filter(|field| missing_paths.contains(&field.path))
+            .map(|field| field.name.to_string())
+            .collect::<HashSet<_>>();
+        let nested_paths = T::config_metadata()
+            .into_iter()
+            .filter(|field| field.path.contains('.'))
+            .map(|field| (field.name, field.path))
+            .collect::<Vec<_>>();
+        let positional = T::config_metadata()
+            .into_iter()
+            .filter(|field| field.positional)
+            .map(|field| field.name)
+            .collect::<HashSet<_>>();
+        let cli_skip = T::config_metadata()
+            .into_iter()
+            .filter(|field| field.cli_skip)
+            .map(|field| field.name)
+            .collect::<HashSet<_>>();
+        let cli_names = T::config_metadata()
+            .into_iter()
+            .filter_map(|field| Some((field.name, field.cli?)))
+            .collect::<HashMap<_, _>>();
+        let subcommand_field = T::config_metadata()
+            .into_iter()
+            .find(|field| field.subcommand)
+            .map(|field| field.name);
 
         let mut cmd = T::command();
 
         cmd = cmd.mut_args(|arg| {
-            let id_str = arg.get_id().to_string();
-
-            let arg = arg.index(None);
-            if missing_required.contains(&id_str) {
-                if arg.get_long().is_none() {
-                    arg.long(&id_str)
-                } else {
-                    arg
-                }
-            } else {
-                arg.required(false)
-            }
+            Self::adjust_arg(arg, &cli_skip, &positional, &cli_names, &missing_required)
         });
 
-        let matches = cmd.get_matches();
+        let groups = CliGroup::from_command(&cmd);
+
+        // Other sources can satisfy required groups, they are checked after merging the CLI
+        for group in groups.iter().filter(|group| group.required) {
+            cmd = cmd.mut_group(&group.id, |group| group.required(false));
+        }
+
+        // The config location was already resolved, clap only needs to accept the flag
+        if let Some((_, flag)) = &self.config_location {
+            cmd = cmd.arg(clap::Arg::new(flag).long(flag).num_args(1));
+        }
+
+        cmd = self.with_print_config_args(cmd);
+
+        let (mut cmd, custom_args) = self.customize_command(cmd);
+        // Unknown args are meant for other parts of the binary, the known ones are still checked
+        let args = if self.ignore_unknown_args {
+            cmd.build();
+            known_args(&cmd, &args)
+        } else {
+            args
+        };
+        let matches = cmd.get_matches_from(&args);
+        let print_format = matches
+            .try_get_one::<String>(PRINT_CONFIG_FORMAT)
+            .ok()
+            .flatten()
+            .cloned();
+
+        let mut value = self.arg_matches_to_value(&matches, &missing_required, subcommand_field);
+        if let Value::Object(map) = &mut value {
+            if let Some((_, flag)) = &self.config_location {
+                map.remove(flag);
+            }
+            map.remove(PRINT_CONFIG);
+            map.remove(PRINT_CONFIG_FORMAT);
+            for id in &custom_args {
+                map.remove(id);
+            }
+            map.retain(|key, _| !cli_skip.contains(key.as_str()));
+
+            for group in &groups {
+                group.resolve_exclusive(map);
+            }
+        }
+
+        if let Value::Object(map) = &mut value {
+            let nested = nested_paths
+                .iter()
+                .filter_map(|(name, path)| Some(merge::at_path(path, map.remove(*name)?)))
+                .collect::<Vec<_>>();
+            value = nested.into_iter().fold(value, merge::merge);
+        }
+
+        if print_config {
+            let merged = self.merge_json(current_config.clone(), value, Source::Cli, provenance);
+            let Err(e) = self.print_config::<T>(
+                merged,
+                provenance,
+                print_format.as_deref().unwrap_or("toml"),
+            );
+            return Err(e);
+        }
+
+        let merged = merge::merge(current_config.clone(), value.clone());
+
+        for group in &groups {
+            group.check_required(&merged)?;
+        }
+
+        Ok((value, matches))
+    }
+
+    /// Applies the command customizers, and returns the ids of the args they added.
+    fn customize_command(&self, mut cmd: clap::Command) -> (clap::Command, Vec<String>) {
+        let konfik_args = cmd
+            .get_arguments()
+            .map(|arg| arg.get_id().to_string())
+            .collect::<HashSet<_>>();
+        for customizer in &self.command_customizers {
+            cmd = customizer(cmd);
+        }
+
+        // Args of customizers don't belong to any field
+        let custom_args = cmd
+            .get_arguments()
+            .map(|arg| arg.get_id().to_string())
+            .filter(|id| !konfik_args.contains(id))
+            .collect();
+        (cmd, custom_args)
+    }
+
+    /// Makes `arg` optional unless its field is still missing, and gives it a long flag.
+    fn adjust_arg(
+        arg: clap::Arg,
+        cli_skip: &HashSet<&str>,
+        positional: &HashSet<&str>,
+        cli_names: &HashMap<&str, &'static str>,
+        missing_required: &HashSet<String>,
+    ) -> clap::Arg {
+        let id_str = arg.get_id().to_string();
+
+        // clap can't remove args, so skipped ones are hidden and their values dropped
+        if cli_skip.contains(id_str.as_str()) {
+            return arg.hide(true).required(false);
+        }
+
+        // Positional args keep their index, they are only made optional
+        // when another source already provided their value
+        if positional.contains(id_str.as_str()) {
+            return if missing_required.contains(&id_str) {
+                arg
+            } else {
+                arg.required(false)
+            };
+        }
 
-        Self::arg_matches_to_value(&matches, &missing_required)
+        let mut arg = arg.index(None);
+        if let Some(flag) = cli_names.get(id_str.as_str()) {
+            arg = arg.long(*flag);
+        }
+        if missing_required.contains(&id_str) {
+            if arg.get_long().is_none() {
+                arg.long(&id_str)
+            } else {
+                arg
+            }
+        } else if arg.get_long().is_none() && arg.get_short().is_none() {
+            arg.long(&id_str).required(false)
+        } else {
+            arg.required(false)
+        }
     }
 
     #[expect(clippy::too_many_lines, clippy::cognitive_complexity)]
     fn arg_matches_to_value(
+        &self,
         matches: &ArgMatches,
-        required_fields: &std::collections::HashSet<String>,
+        required_fields: &HashSet<String>,
+        subcommand_field: Option<&str>,
     ) -> Value {
         use clap::Id;
 
@@ -177,16 +404,106 @@ impl ConfigLoader {
 
         // Subcommand
         if let Some((sub_name, sub_matches)) = matches.subcommand() {
-            obj.insert(
-                "_subcommand".to_string(),
-                Value::String(sub_name.to_string()),
-            );
-            obj.insert(
-                sub_name.to_string(),
-                Self::arg_matches_to_value(sub_matches, required_fields),
-            );
+            let mut sub_value = self.arg_matches_to_value(sub_matches, required_fields, None);
+
+            if let (Some(field), Value::Object(sub_map)) = (subcommand_field, &mut sub_value) {
+                // Internally tagged enum: the args and the tag share one object
+                sub_map.insert(
+                    self.subcommand_tag.clone(),
+                    Value::String(sub_name.to_string()),
+                );
+                obj.insert(field.to_string(), sub_value);
+            } else {
+                obj.insert(
+                    self.subcommand_tag.clone(),
+                    Value::String(sub_name.to_string()),
+                );
+                obj.insert(sub_name.to_string(), sub_value);
+            }
         }
 
         Value::Object(obj)
     }
 }
+
+/// The `args` that `cmd` knows, starting with the name of the command.
+///
+/// Unknown flags are dropped together with the arg following them, unless it starts with
+/// `-` or the flag has its value attached, e.g. `--level=debug`. Everything after `--` is
+/// kept, the args of subcommands are filtered against the subcommand.
+fn known_args(cmd: &clap::Command, args: &[OsString]) -> Vec<OsString> {
+    let Some((name, args)) = args.split_first() else {
+        return Vec::new();
+    };
+    let mut known = vec![name.clone()];
+    let mut rest = args.iter();
+
+    while let Some(arg) = rest.next() {
+        let Some(flag) = arg.to_str().filter(|_| is_flag(arg)) else {
+            if let Some(subcommand) = cmd.find_subcommand(arg) {
+                known.extend(known_args(subcommand, &args[args.len() - rest.len() - 1..]));
+                return known;
+            }
+            known.push(arg.clone());
+            continue;
+        };
+
+        if flag == "--" {
+            known.push(arg.clone());
+            known.extend(rest.cloned());
+            return known;
+        }
+
+        let (found, attached) = find_flag(cmd, flag);
+        if let Some(found) = found {
+            known.push(arg.clone());
+            if found.get_action().takes_values()
+                && !attached
+                && let Some(value) = rest.next()
+            {
+                known.push(value.clone());
+            }
+        } else if !attached && rest.as_slice().first().is_some_and(|next| !is_flag(next)) {
+            // The value of an unknown flag can't be told apart from a positional arg
+            rest.next();
+        }
+    }
+
+    known
+}
+
+/// If `arg` is a flag instead of a value.
+fn is_flag(arg: &OsString) -> bool {
+    arg.to_str()
+        .is_some_and(|arg| arg.starts_with('-') && arg.len() > 1)
+}
+
+/// The arg of `cmd` that `flag` sets, and if `flag` has its value attached.
+///
+/// Bundled short flags like `-vq` count as an attached value of `-v`.
+fn find_flag<'a>(cmd: &'a clap::Command, flag: &str) -> (Option<&'a clap::Arg>, bool) {
+    if let Some(long) = flag.strip_prefix("--") {
+        let (name, attached) = long
+            .split_once('=')
+            .map_or((long, false), |(name, _)| (name, true));
+        let found = cmd.get_arguments().find(|arg| {
+            arg.get_long() == Some(name)
+                || arg
+                    .get_all_aliases()
+                    .is_some_and(|aliases| aliases.contains(&name))
+        });
+        return (found, attached);
+    }
+
+    let mut shorts = flag.chars().skip(1);
+    let Some(short) = shorts.next() else {
+        return (None, false);
+    };
+    let found = cmd.get_arguments().find(|arg| {
+        arg.get_short() == Some(short)
+            || arg
+                .get_all_short_aliases()
+                .is_some_and(|aliases| aliases.contains(&short))
+    });
+    (found, shorts.next().is_some())
+}
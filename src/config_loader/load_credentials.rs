@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigLoader;
+use crate::{Error, config_meta::ConfigMeta, merge};
+use serde_json::{Map, Value};
+use std::fs;
+
+impl ConfigLoader {
+    /// Reads the systemd credentials in `$CREDENTIALS_DIRECTORY` that map to a field of `T`.
+    pub(super) fn load_credentials<T: ConfigMeta>(&self) -> Result<Option<Value>, Error> {
+        let Some(names) = &self.systemd_credentials else {
+            return Ok(None);
+        };
+        let Some(dir) = self.var_os("CREDENTIALS_DIRECTORY") else {
+            return Ok(None);
+        };
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Ok(None);
+        };
+
+        let metadata = T::config_metadata();
+        let mut config = Value::Object(Map::new());
+
+        for entry in entries {
+            let entry = entry?;
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+
+            let path = names.get(&name).cloned().or_else(|| {
+                let normalized = name.to_lowercase().replace('-', "_");
+                metadata
+                    .iter()
+                    .find(|field| !field.skip && field.path == normalized)
+                    .map(|field| field.path.clone())
+            });
+            let Some(path) = path else {
+                continue;
+            };
+
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            let content = fs::read_to_string(entry.path())?;
+            let content = if self.env_trim {
+                content.trim()
+            } else {
+                &content
+            };
+
+            let value = merge::at_path(&path, merge::parse_value(content));
+            config = merge::merge(config, value);
+        }
+
+        Ok(Some(config))
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, load_file::FileFormat};
+use crate::Error;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+impl ConfigLoader {
+    /// Lists the config files of a layered directory in merge order.
+    ///
+    /// These are `config.json`, `config.yaml` and `config.toml` followed by every
+    /// supported file in `conf.d`, sorted by file name. Missing directories yield no files.
+    pub(super) fn dir_config_files(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut files = vec![
+            dir.join("config.json"),
+            dir.join("config.yaml"),
+            dir.join("config.toml"),
+        ];
+
+        let fragments_dir = dir.join("conf.d");
+        if fragments_dir.is_dir() {
+            let mut fragments = fs::read_dir(&fragments_dir)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            fragments.retain(|path| {
+                path.is_file()
+                    && path
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| ext.parse::<FileFormat>().is_ok())
+            });
+            fragments.sort();
+
+            files.extend(fragments);
+        }
+
+        Ok(files)
+    }
+
+    /// Lists the files named `filename` in the current directory and its parents in merge order.
+    ///
+    /// The search ends at the filesystem root or the first directory containing `.git`.
+    /// The files are ordered from the outermost to the innermost directory, so closer files
+    /// override the ones further up. With `nearest_only` only the innermost file is returned.
+    pub(super) fn ancestor_config_files(
+        filename: &Path,
+        nearest_only: bool,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let cwd = std::env::current_dir()?;
+        let mut files = Vec::new();
+
+        for dir in cwd.ancestors() {
+            let path = dir.join(filename);
+            if path.is_file() {
+                files.push(path);
+                if nearest_only {
+                    break;
+                }
+            }
+
+            if dir.join(".git").exists() {
+                break;
+            }
+        }
+
+        files.reverse();
+        Ok(files)
+    }
+}
@@ -2,38 +2,315 @@
 // Copyright (c) 2025 kingananas20
 
 use super::ConfigLoader;
-use crate::config_meta::ConfigMeta;
+use crate::{
+    Error,
+    config_meta::{ConfigMeta, FieldMeta},
+    merge,
+};
 use serde_json::{Map, Value};
-use std::env;
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+};
 
 impl ConfigLoader {
-    pub(super) fn load_env<T: ConfigMeta>(&self) -> Value {
-        let mut env_map = Map::new();
+    /// Builds the env layer from the env vars of the fields of `T`.
+    ///
+    /// Only the names the fields map to are looked up, the rest of the environment is
+    /// never copied. A snapshot of the whole environment only pays off for very wide
+    /// configs in small environments, and costs every other config the size of the
+    /// environment (see `benches/load.rs` and `benches/wide_env.rs`).
+    ///
+    /// Set variables that aren't valid UTF-8 fail with an [`Error::Environment`] instead of
+    /// being treated as unset.
+    pub(super) fn load_env<T: ConfigMeta>(&self) -> Result<Value, Error> {
+        let mut config = Value::Object(Map::new());
         let metadata = T::config_metadata();
 
         for field in &metadata {
-            let path_upper = field
-                .path
-                .split('.')
-                .map(str::to_uppercase)
-                .collect::<Vec<_>>()
-                .join("_");
-
-            let env_var = self
-                .env_prefix
-                .as_ref()
-                .map_or(path_upper.clone(), |prefix| {
-                    if prefix.is_empty() {
-                        return path_upper;
-                    }
-                    format!("{}_{path_upper}", prefix.to_uppercase())
-                });
-
-            if let Ok(value) = env::var(&env_var) {
-                env_map.insert(field.name.to_string(), Self::parse_env_value(&value));
+            let value = if field.env.is_empty() {
+                self.env_value(&self.env_var_name(&field.path))?
+            } else {
+                field
+                    .env
+                    .iter()
+                    .map(|env_var| self.env_value(&self.explicit_env_name(env_var)))
+                    .find_map(Result::transpose)
+                    .transpose()?
+            };
+
+            if let Some(value) = value {
+                let value = if self.env_trim { value.trim() } else { &value };
+                let value = merge::at_path(&field.path, merge::parse_value(value));
+                config = merge::merge(config, value);
+            }
+
+            // Deprecated names are read too, they are moved to the field after merging
+            let parent = field.path.rsplit_once('.').map(|(parent, _)| parent);
+            for alias in field.deprecated_aliases {
+                let alias_path =
+                    parent.map_or_else(|| (*alias).to_string(), |p| format!("{p}.{alias}"));
+                if let Some(value) = self.env_value(&self.env_var_name(&alias_path))? {
+                    let value = if self.env_trim { value.trim() } else { &value };
+                    let value = merge::at_path(&alias_path, merge::parse_value(value));
+                    config = merge::merge(config, value);
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// If an env var [`load_env`](Self::load_env) reads for `field` is set.
+    ///
+    /// Set variables that aren't valid UTF-8 count too, loading reports them.
+    pub(super) fn env_provides(&self, field: &FieldMeta) -> bool {
+        let is_set = |name: &str| !matches!(self.env_value(name), Ok(None));
+        let parent = field.path.rsplit_once('.').map(|(parent, _)| parent);
+        let aliased = field.deprecated_aliases.iter().any(|alias| {
+            let alias_path =
+                parent.map_or_else(|| (*alias).to_string(), |p| format!("{p}.{alias}"));
+            is_set(&self.env_var_name(&alias_path))
+        });
+
+        aliased
+            || if field.env.is_empty() {
+                is_set(&self.env_var_name(&field.path))
+            } else {
+                field
+                    .env
+                    .iter()
+                    .any(|env_var| is_set(&self.explicit_env_name(env_var)))
+            }
+    }
+
+    /// Resolves the config like [`load_merged`](Self::load_merged) and turns it back into env vars
+    ///
+    /// Every field that has a value becomes a pair of the env var name [`load`](Self::load)
+    /// would read it from and its value. Strings are exported as is, other values as JSON.
+    /// This is the inverse of the env source, e.g. to pass the resolved config to a child
+    /// process.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik, Nested};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     hosts: Vec<String>,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_export_env.toml");
+    /// std::fs::write(&path, "port = 8080\nhosts = [\"a\", \"b\"]\n[database]\nurl = \"postgres://db\"")?;
+    ///
+    /// let vars = ConfigLoader::default()
+    ///     .with_env_prefix("EXPORT")
+    ///     .with_config_file(&path)
+    ///     .export_env::<AppConfig>()?;
+    ///
+    /// assert_eq!(vars, [
+    ///     ("EXPORT_PORT".to_string(), "8080".to_string()),
+    ///     ("EXPORT_HOSTS".to_string(), r#"["a","b"]"#.to_string()),
+    ///     ("EXPORT_DATABASE_URL".to_string(), "postgres://db".to_string()),
+    /// ]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`load_merged`](Self::load_merged).
+    pub fn export_env<T: ConfigMeta>(&self) -> Result<Vec<(String, String)>, Error> {
+        let config = self.load_merged::<T>()?;
+
+        Ok(T::config_metadata()
+            .into_iter()
+            .filter(|field| !field.skip)
+            .filter_map(|field| {
+                let value = match T::get_nested_value(&config, &field.path)? {
+                    Value::Null => return None,
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                let env_var = field.env.first().map_or_else(
+                    || self.env_var_name(&field.path),
+                    |env_var| self.explicit_env_name(env_var),
+                );
+                Some((env_var, value))
+            })
+            .collect())
+    }
+
+    /// Parses the override env var, e.g. `logging.level=debug;port=9000`, into a nested object.
+    pub(super) fn load_env_override(&self) -> Result<Option<Value>, Error> {
+        let Some((var, separator)) = &self.env_override else {
+            return Ok(None);
+        };
+        let Some(overrides) = self.var_os(var) else {
+            return Ok(None);
+        };
+        let overrides = overrides.to_str().ok_or_else(|| {
+            Error::Environment(format!("{var} is set, but its value isn't valid UTF-8"))
+        })?;
+
+        let mut config = Value::Object(Map::new());
+        for entry in overrides.split(*separator) {
+            if entry.trim().is_empty() {
+                continue;
+            }
+
+            let Some((path, value)) = entry.split_once('=') else {
+                return Err(Error::Environment(format!(
+                    "malformed override `{entry}` in {var}, expected `path=value`"
+                )));
+            };
+
+            let path = path.trim();
+            if path.is_empty() || path.split('.').any(str::is_empty) {
+                return Err(Error::Environment(format!(
+                    "invalid override path `{path}` in {var}"
+                )));
+            }
+
+            let value = if self.env_trim { value.trim() } else { value };
+            let value = merge::at_path(path, merge::parse_value(value));
+            config = merge::merge(config, value);
+        }
+
+        Ok(Some(config))
+    }
+
+    /// Collects the env vars matching the env globs into maps at their paths.
+    pub(super) fn load_env_globs(&self) -> Result<Option<Value>, Error> {
+        if self.env_globs.is_empty() {
+            return Ok(None);
+        }
+
+        let patterns = self
+            .env_globs
+            .iter()
+            .map(|(path, pattern)| (path, pattern.split_once('*').unwrap_or((pattern, ""))))
+            .collect::<Vec<_>>();
+        let vars = self.env_matching(|name| {
+            patterns
+                .iter()
+                .any(|(_, (prefix, _))| name.starts_with(prefix))
+        });
+        let mut config = Value::Object(Map::new());
+
+        for (path, (prefix, suffix)) in patterns {
+            let mut map = Map::new();
+            for (name, value) in &vars {
+                if name.len() <= prefix.len() + suffix.len() {
+                    continue;
+                }
+                let Some(key) = name
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix))
+                else {
+                    continue;
+                };
+                let value = utf8_value(name, value)?;
+                let value = if self.env_trim { value.trim() } else { value };
+                map.insert(key.to_lowercase(), merge::parse_value(value));
+            }
+
+            if !map.is_empty() {
+                config = merge::merge(config, merge::at_path(path, Value::Object(map)));
             }
         }
 
-        Value::Object(env_map)
+        Ok(Some(config))
+    }
+
+    /// Reads the env var `name`, failing if it isn't valid UTF-8.
+    ///
+    /// With an allowlist, variables that aren't allowlisted are unset.
+    fn env_value(&self, name: &str) -> Result<Option<String>, Error> {
+        if self
+            .env_allowlist
+            .as_ref()
+            .is_some_and(|allowlist| !allowlist.iter().any(|var| var == name))
+        {
+            return Ok(None);
+        }
+
+        self.var_os(name)
+            .map(|value| utf8_value(name, &value).map(str::to_string))
+            .transpose()
+    }
+
+    /// Reads the env var `name` from the [injected vars](Self::with_env_vars), or else from
+    /// the process environment.
+    pub(super) fn var_os(&self, name: &str) -> Option<OsString> {
+        self.env_vars
+            .as_ref()
+            .map_or_else(|| env::var_os(name), |vars| vars.get(name).cloned())
+    }
+
+    /// Collects the env vars with valid UTF-8 names that `filter` accepts.
+    ///
+    /// Names are filtered before anything is copied. With an allowlist only the
+    /// allowlisted variables are read.
+    fn env_matching(&self, filter: impl Fn(&str) -> bool) -> Vec<(String, OsString)> {
+        if let Some(allowlist) = &self.env_allowlist {
+            return allowlist
+                .iter()
+                .filter(|name| filter(name))
+                .filter_map(|name| Some((name.clone(), self.var_os(name)?)))
+                .collect();
+        }
+
+        if let Some(vars) = &self.env_vars {
+            return vars
+                .iter()
+                .filter(|(name, _)| filter(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+        }
+
+        env::vars_os()
+            .filter(|(name, _)| name.to_str().is_some_and(&filter))
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value)))
+            .collect()
     }
+
+    /// Computes the environment variable name for a dotted field path.
+    fn env_var_name(&self, path: &str) -> String {
+        let path_upper = path
+            .split('.')
+            .map(str::to_uppercase)
+            .collect::<Vec<_>>()
+            .join("_");
+
+        self.prefixed(path_upper)
+    }
+
+    /// The env var name of a `#[konfik(env = "...")]` name, absolute names starting with `=`
+    /// don't get the prefix.
+    fn explicit_env_name(&self, name: &str) -> String {
+        name.strip_prefix('=')
+            .map_or_else(|| self.prefixed(name.to_string()), str::to_string)
+    }
+
+    /// `name` with the env prefix, if there is one.
+    fn prefixed(&self, name: String) -> String {
+        match &self.env_prefix {
+            Some(prefix) if !prefix.is_empty() => format!("{}_{name}", prefix.to_uppercase()),
+            _ => name,
+        }
+    }
+}
+
+/// The value of the env var `name` as UTF-8.
+fn utf8_value<'a>(name: &str, value: &'a OsStr) -> Result<&'a str, Error> {
+    value.to_str().ok_or_else(|| {
+        Error::Environment(format!("{name} is set, but its value isn't valid UTF-8"))
+    })
 }
@@ -4,59 +4,191 @@
 use super::ConfigLoader;
 use crate::Error;
 use std::{
-    fs,
-    path::Path,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
     str::{self, FromStr},
 };
 
 impl ConfigLoader {
-    pub(super) fn load_file<P: AsRef<Path>>(path: P) -> Result<Option<serde_json::Value>, Error> {
+    /// Loads the file at `path`, as `format` or in the format of its extension.
+    pub(super) fn load_file<P: AsRef<Path>>(
+        path: P,
+        format: Option<FileFormat>,
+    ) -> Result<Option<serde_json::Value>, Error> {
         if !path.as_ref().exists() {
             return Ok(None);
         }
 
-        let content = fs::read_to_string(&path)?;
-        let file_format: FileFormat = path
-            .as_ref()
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("json")
-            .parse()?;
-        let value = Self::parse_file_content(content, file_format);
+        if path.as_ref().is_dir() {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!(
+                    "expected a file, got directory: {} (use `with_layered_dirs` to load config directories)",
+                    path.as_ref().display()
+                ),
+            )));
+        }
+
+        let file_format = match format {
+            Some(format) => format,
+            None => path
+                .as_ref()
+                .extension()
+                .and_then(|s| s.to_str())
+                .unwrap_or("json")
+                .parse()?,
+        };
 
-        Ok(value)
+        let content = fs::read_to_string(&path).map_err(|e| read_error(path.as_ref(), e))?;
+        let value = Self::parse_file_content(&content, file_format)
+            .map_err(|e| file_error(path.as_ref().to_path_buf(), &content, e))?;
+
+        Ok(Some(value))
     }
 
-    fn parse_file_content(content: String, file_format: FileFormat) -> Option<serde_json::Value> {
+    pub(super) fn parse_file_content(
+        content: &str,
+        file_format: FileFormat,
+    ) -> Result<serde_json::Value, Error> {
         match file_format {
-            FileFormat::Json => {
-                if let Ok(v) = serde_json::from_str(&content) {
-                    return Some(v);
-                }
-            }
-            FileFormat::Yaml => {
-                if let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(&content) {
-                    if let Ok(v) = serde_json::to_value(yaml) {
-                        return Some(v);
-                    }
-                }
-            }
-            FileFormat::Toml => {
-                if let Ok(toml) = toml::from_str::<toml::Value>(&content) {
-                    if let Ok(v) = serde_json::to_value(toml) {
-                        return Some(v);
-                    }
-                }
-            }
+            FileFormat::Json => Ok(serde_json::from_str(content)?),
+            FileFormat::Yaml => Self::parse_yaml(content),
+            FileFormat::Toml => Ok(toml_to_json(toml::from_str(content)?)),
+        }
+    }
+
+    /// Parses YAML with the `serde_yml` backend.
+    #[cfg(feature = "yaml-serde_yml")]
+    fn parse_yaml(content: &str) -> Result<serde_json::Value, Error> {
+        let yaml = serde_yml::from_str::<serde_yml::Value>(content)?;
+        Ok(serde_json::to_value(yaml)?)
+    }
+
+    /// Parses YAML with the `serde_yaml` backend.
+    #[cfg(all(feature = "yaml-serde_yaml", not(feature = "yaml-serde_yml")))]
+    fn parse_yaml(content: &str) -> Result<serde_json::Value, Error> {
+        let yaml = serde_yaml::from_str::<serde_yaml::Value>(content)?;
+        Ok(serde_json::to_value(yaml)?)
+    }
+
+    /// Fails for every YAML input since no YAML backend is enabled.
+    #[cfg(not(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml")))]
+    fn parse_yaml(_content: &str) -> Result<serde_json::Value, Error> {
+        Err(Error::Yaml(
+            "no YAML backend enabled, enable the `yaml-serde_yaml` or `yaml-serde_yml` feature"
+                .into(),
+        ))
+    }
+}
+
+/// Turns an error reading the file at `path` into an [`Error::PermissionDenied`] or an
+/// [`Error::Io`].
+fn read_error(path: &Path, e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::PermissionDenied {
+        Error::PermissionDenied {
+            path: path.to_path_buf(),
+            source: e,
         }
+    } else {
+        Error::Io(e)
+    }
+}
+
+/// Builds an [`Error::File`] that keeps only the line of `content` the parse error points at.
+pub(super) fn file_error(path: PathBuf, content: &str, source: Error) -> Error {
+    let (snippet, span) = error_range(content, &source)
+        .and_then(|range| snippet(content, range))
+        .unzip();
+
+    // TOML errors hold the whole input for their message, keep only the message
+    let source = match source {
+        Error::Toml(e) => Error::Toml(serde::de::Error::custom(e.to_string().trim_end())),
+        source => source,
+    };
+
+    Error::File {
+        path,
+        snippet: snippet.unwrap_or_default(),
+        span,
+        source: Box::new(source),
+    }
+}
+
+/// Byte range of a TOML or JSON parse error in `content`.
+fn error_range(content: &str, error: &Error) -> Option<Range<usize>> {
+    match error {
+        Error::Toml(e) => e.span(),
+        Error::Serde(e) if e.line() > 0 => {
+            let start = offset(content, e.line(), e.column());
+            Some(start..start)
+        }
+        _ => None,
+    }
+}
+
+/// The line of `content` containing the start of `range`, and `range` relative to it.
+fn snippet(content: &str, range: Range<usize>) -> Option<(String, Range<usize>)> {
+    let start = range.start.min(content.len());
+    // Errors at the end of the file belong to its last line, not the empty one after it
+    let before = content.get(..start)?;
+    let before = if start == content.len() {
+        before.strip_suffix('\n').unwrap_or(before)
+    } else {
+        before
+    };
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[line_start..]
+        .find('\n')
+        .map_or(content.len(), |i| line_start + i);
+    let start = start.min(line_end);
+    let end = range.end.clamp(start, line_end);
+
+    Some((
+        content[line_start..line_end].to_string(),
+        start - line_start..end - line_start,
+    ))
+}
+
+/// Byte offset of a 1-based line and column in `content`.
+fn offset(content: &str, line: usize, column: usize) -> usize {
+    let line_start = content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+
+    (line_start + column.saturating_sub(1)).min(content.len())
+}
+
+/// Converts TOML to JSON, dates and times become their RFC 3339 strings.
+fn toml_to_json(value: toml::Value) -> serde_json::Value {
+    use serde_json::Value;
 
-        None
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::Number(i.into()),
+        toml::Value::Float(f) => serde_json::Number::from_f64(f).map_or(Value::Null, Value::Number),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
     }
 }
 
-enum FileFormat {
+/// Format of a configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// JSON
     Json,
+    /// YAML
     Yaml,
+    /// TOML
     Toml,
 }
 
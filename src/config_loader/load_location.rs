@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigLoader;
+#[cfg(feature = "remote")]
+use super::load_file::FileFormat;
+use crate::Error;
+use serde_json::Value;
+use std::{env, ffi::OsString, io, path::Path};
+
+impl ConfigLoader {
+    /// Finds the config location passed with the configured CLI flag or env var.
+    ///
+    /// The CLI flag wins over the env var.
+    pub(super) fn config_location(&self) -> Option<String> {
+        let (env_var, flag) = self.config_location.as_ref()?;
+
+        let args = self
+            .cli_args
+            .clone()
+            .unwrap_or_else(|| env::args_os().collect());
+        let long = format!("--{flag}");
+        let long_eq = format!("--{flag}=");
+
+        let mut args = args.iter().skip(1).map(OsString::as_os_str);
+        while let Some(arg) = args.next() {
+            let Some(arg) = arg.to_str() else {
+                continue;
+            };
+
+            if arg == long {
+                return args
+                    .next()
+                    .map(|value| value.to_string_lossy().into_owned());
+            }
+            if let Some(value) = arg.strip_prefix(&long_eq) {
+                return Some(value.to_string());
+            }
+        }
+
+        self.var_os(env_var)?.into_string().ok()
+    }
+
+    /// Loads the config referenced by a location, either a file path, a
+    /// `file://` URL or, with the `remote` feature, a `http(s)://` URL.
+    pub(super) fn load_location(&self, location: &str) -> Result<Value, Error> {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            return self.fetch(location);
+        }
+
+        let path = Path::new(location.strip_prefix("file://").unwrap_or(location));
+        Self::load_file(path, None)?.ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("config location not found: {}", path.display()),
+            ))
+        })
+    }
+
+    /// Fetches and parses the config document at `url`.
+    ///
+    /// The format is taken from the `Content-Type` of the response, then from the
+    /// extension of the URL and defaults to JSON.
+    #[cfg(feature = "remote")]
+    pub(super) fn fetch(&self, url: &str) -> Result<Value, Error> {
+        let mut request = ureq::get(url);
+        for (name, value) in &self.remote_headers {
+            request = request.header(name, value);
+        }
+        let mut response = request
+            .config()
+            .timeout_global(self.remote_timeout)
+            .build()
+            .call()
+            .map_err(|e| Error::Remote(format!("{url}: {e}")))?;
+
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .and_then(content_type_format);
+        let content = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| Error::Remote(format!("{url}: {e}")))?;
+
+        let file_format = match content_type {
+            Some(format) => format,
+            None => Path::new(url.split(['?', '#']).next().unwrap_or(url))
+                .extension()
+                .and_then(|s| s.to_str())
+                .unwrap_or("json")
+                .parse::<FileFormat>()?,
+        };
+
+        Self::parse_file_content(&content, file_format)
+            .map_err(|e| super::load_file::file_error(url.into(), &content, e))
+    }
+
+    #[cfg(not(feature = "remote"))]
+    #[expect(clippy::unused_self)]
+    pub(super) fn fetch(&self, url: &str) -> Result<Value, Error> {
+        Err(Error::Remote(format!(
+            "{url}: loading config over HTTP requires the `remote` feature"
+        )))
+    }
+}
+
+/// The format of a `Content-Type` header value, `None` for generic types like `text/plain`.
+#[cfg(feature = "remote")]
+fn content_type_format(content_type: &str) -> Option<FileFormat> {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    let subtype = mime.rsplit(['/', '+']).next().unwrap_or_default();
+    match subtype.to_ascii_lowercase().as_str() {
+        "json" => Some(FileFormat::Json),
+        "yaml" | "x-yaml" => Some(FileFormat::Yaml),
+        "toml" | "x-toml" => Some(FileFormat::Toml),
+        _ => None,
+    }
+}
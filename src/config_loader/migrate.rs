@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigLoader;
+use crate::Error;
+use serde_json::Value;
+
+impl ConfigLoader {
+    /// Runs the migrations starting at the `version` of `config` until none is left.
+    ///
+    /// A missing `version` is version 1. After every migration `version` is set to the
+    /// next version, so migrations don't have to update it themselves. Runs on the merged
+    /// sources before any default is filled in, so defaults neither hide a missing version
+    /// nor occupy the keys migrations move values to.
+    pub(super) fn migrate(&self, config: &mut Value) -> Result<(), Error> {
+        if self.migrations.is_empty() {
+            return Ok(());
+        }
+
+        let version = match config.get("version") {
+            None | Some(Value::Null) => Some(1),
+            Some(Value::Number(n)) => n.as_u64(),
+            Some(Value::String(s)) => s.parse().ok(),
+            Some(_) => None,
+        };
+        let Some(mut version) = version else {
+            return Err(Error::Validation(format!(
+                "invalid config version {}, expected a positive integer",
+                config["version"]
+            )));
+        };
+
+        while let Some((_, migration)) = self.migrations.iter().find(|(from, _)| *from == version) {
+            let migrated = migration(config.take())
+                .map_err(|e| Error::Validation(format!("migration from v{version} failed: {e}")))?;
+            *config = migrated;
+            version += 1;
+
+            if let Value::Object(map) = config {
+                map.insert("version".to_string(), version.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::{
+    Error,
+    config_meta::{ConfigMeta, FieldType},
+    merge,
+};
+use serde_json::{Number, Value};
+
+/// Names of the integer scalar types
+const INTEGERS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+impl ConfigLoader {
+    /// Normalizes the merged config according to the field metadata of `T`.
+    ///
+    /// Values at redacted paths are left out of the errors.
+    pub(super) fn normalize<T: ConfigMeta>(
+        &self,
+        config: &mut Value,
+        provenance: &Provenance,
+    ) -> Result<(), Error> {
+        for field in T::config_metadata() {
+            if field.skip {
+                continue;
+            }
+
+            let Some(value) = get_nested_value_mut(config, &field.path) else {
+                continue;
+            };
+
+            if let Some(transform) = field.transform {
+                *value = transform(value.take())?;
+            }
+
+            if let Some((true_values, false_values)) = &self.bool_literals {
+                coerce_bool_literals(value, &field.ty, true_values, false_values).map_err(
+                    |invalid| {
+                        Error::Validation(format!(
+                            "invalid bool for {}: {}, expected one of {}",
+                            field.path,
+                            self.shown(&field.path, &invalid, provenance),
+                            true_values
+                                .iter()
+                                .chain(false_values)
+                                .map(String::as_str)
+                                .chain(["true", "false"])
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ))
+                    },
+                )?;
+            }
+            if self.coerce_scalar_strings {
+                coerce_scalar_strings(value, &field.ty);
+            }
+            coerce_numbers(value, &field.ty);
+
+            if field.size
+                && let Value::String(size) = value
+            {
+                let bytes = parse_size(size).ok_or_else(|| {
+                    Error::Validation(format!(
+                        "invalid size for {}: {}",
+                        field.path,
+                        self.shown(&field.path, size, provenance)
+                    ))
+                })?;
+                *value = Value::Number(bytes.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renames keys of a file that match a field of `T` except for case, if enabled.
+    pub(super) fn match_case<T: ConfigMeta>(&self, mut file_config: Value) -> Result<Value, Error> {
+        if !self.case_insensitive_keys {
+            return Ok(file_config);
+        }
+
+        let paths = T::config_metadata()
+            .into_iter()
+            .map(|field| field.path)
+            .collect::<Vec<_>>();
+        match_key_case(&mut file_config, "", &paths)?;
+        Ok(file_config)
+    }
+
+    /// Moves values set under a deprecated alias to their field and warns about them.
+    ///
+    /// A value under the field's current name wins over the deprecated one.
+    pub(super) fn rename_deprecated<T: ConfigMeta>(&self, config: &mut Value) {
+        for field in T::config_metadata() {
+            if field.deprecated_aliases.is_empty() {
+                continue;
+            }
+
+            let (parent, name) = field.path.rsplit_once('.').unwrap_or(("", &field.path));
+            let parent_value = if parent.is_empty() {
+                Some(&mut *config)
+            } else {
+                get_nested_value_mut(config, parent)
+            };
+            let Some(Value::Object(map)) = parent_value else {
+                continue;
+            };
+
+            for alias in field.deprecated_aliases {
+                let Some(value) = map.remove(*alias) else {
+                    continue;
+                };
+
+                if let Some(handler) = &self.warning_handler {
+                    let alias_path = if parent.is_empty() {
+                        (*alias).to_string()
+                    } else {
+                        format!("{parent}.{alias}")
+                    };
+                    handler(&format!(
+                        "`{alias_path}` is deprecated, use `{}` instead",
+                        field.path
+                    ));
+                }
+
+                map.entry(name).or_insert(value);
+            }
+        }
+    }
+}
+
+/// Renames the keys of `value` at `prefix` that match a field name of `paths` except for case.
+///
+/// Two keys matching the same field name are ambiguous and rejected.
+fn match_key_case(value: &mut Value, prefix: &str, paths: &[String]) -> Result<(), Error> {
+    let Value::Object(map) = value else {
+        return Ok(());
+    };
+
+    let names = paths
+        .iter()
+        .filter_map(|path| {
+            if prefix.is_empty() {
+                Some(path.as_str())
+            } else {
+                path.strip_prefix(prefix)?.strip_prefix('.')
+            }
+        })
+        .filter_map(|rest| rest.split('.').next())
+        .collect::<Vec<_>>();
+
+    let mut matched = serde_json::Map::new();
+    for (key, mut item) in std::mem::take(map) {
+        let name = names
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(&key))
+            .map_or_else(|| key.clone(), ToString::to_string);
+
+        let path = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match_key_case(&mut item, &path, paths)?;
+
+        if matched.insert(name, item).is_some() {
+            return Err(Error::Validation(format!(
+                "ambiguous keys for {path}, they only differ in case"
+            )));
+        }
+    }
+    *map = matched;
+
+    Ok(())
+}
+
+/// Parses a raw string, e.g. a `default_if_none` value, for a field of type `ty`.
+///
+/// Scalars other than numbers and booleans, like `String`, keep the string as is, so
+/// `"1234"` stays a string. Everything else is parsed like an env value.
+pub(super) fn parse_for_type(value: &str, ty: &FieldType) -> Value {
+    match ty {
+        FieldType::Option(inner) => parse_for_type(value, inner),
+        FieldType::Scalar(name)
+            if *name != "bool" && *name != "f32" && *name != "f64" && !INTEGERS.contains(name) =>
+        {
+            Value::String(value.to_string())
+        }
+        _ => merge::parse_value(value),
+    }
+}
+
+/// Converts numbers to the kind the field type expects.
+///
+/// Integers are widened to floats for float fields, and floats without a fraction
+/// become integers for integer fields. Other values are left to deserialization.
+fn coerce_numbers(value: &mut Value, ty: &FieldType) {
+    match (ty, value) {
+        (FieldType::Option(inner), value) => coerce_numbers(value, inner),
+        (FieldType::Vec(inner), Value::Array(items)) => {
+            for item in items {
+                coerce_numbers(item, inner);
+            }
+        }
+        (FieldType::Scalar("f32" | "f64"), Value::Number(n)) if !n.is_f64() => {
+            if let Some(float) = n.as_f64().and_then(Number::from_f64) {
+                *n = float;
+            }
+        }
+        (FieldType::Scalar(name), Value::Number(n)) if INTEGERS.contains(name) && n.is_f64() => {
+            if let Some(float) = n.as_f64().filter(|f| f.fract() == 0.0) {
+                #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let integer = if float < 0.0 && float >= -(2_f64.powi(63)) {
+                    Some(Number::from(float as i64))
+                } else if float >= 0.0 && float < 2_f64.powi(64) {
+                    Some(Number::from(float as u64))
+                } else {
+                    None
+                };
+                if let Some(integer) = integer {
+                    *n = integer;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses quoted booleans and numbers for fields of those types.
+///
+/// Strings that don't parse as the field type are left to deserialization.
+fn coerce_scalar_strings(value: &mut Value, ty: &FieldType) {
+    match (ty, value) {
+        (FieldType::Option(inner), value) => coerce_scalar_strings(value, inner),
+        (FieldType::Vec(inner), Value::Array(items)) => {
+            for item in items {
+                coerce_scalar_strings(item, inner);
+            }
+        }
+        (FieldType::Scalar("bool"), value @ Value::String(_)) => {
+            if let Some(b) = value.as_str().and_then(|s| s.parse::<bool>().ok()) {
+                *value = Value::Bool(b);
+            }
+        }
+        (FieldType::Scalar(name), value @ Value::String(_))
+            if *name == "f32" || *name == "f64" || INTEGERS.contains(name) =>
+        {
+            if let Some(number @ Value::Number(_)) = value.as_str().map(merge::parse_value) {
+                *value = number;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Maps string values of `bool` fields to booleans using custom literals.
+///
+/// Returns the first string that is neither one of the literals nor `true` or `false`.
+fn coerce_bool_literals(
+    value: &mut Value,
+    ty: &FieldType,
+    true_values: &[String],
+    false_values: &[String],
+) -> Result<(), String> {
+    match (ty, value) {
+        (FieldType::Option(inner), value) => {
+            coerce_bool_literals(value, inner, true_values, false_values)
+        }
+        (FieldType::Vec(inner), Value::Array(items)) => items
+            .iter_mut()
+            .try_for_each(|item| coerce_bool_literals(item, inner, true_values, false_values)),
+        (FieldType::Scalar("bool"), value @ Value::String(_)) => {
+            let literal = value.as_str().unwrap_or_default();
+            let matches = |values: &[String]| {
+                values
+                    .iter()
+                    .any(|value| value.eq_ignore_ascii_case(literal))
+            };
+            let b = if matches(true_values) || literal.eq_ignore_ascii_case("true") {
+                true
+            } else if matches(false_values) || literal.eq_ignore_ascii_case("false") {
+                false
+            } else {
+                return Err(literal.to_string());
+            };
+            *value = Value::Bool(b);
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Mutable counterpart of [`ConfigMeta::get_nested_value`]
+fn get_nested_value_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    let mut current = value;
+    for key in path.split('.') {
+        match current {
+            Value::Object(map) => current = map.get_mut(key)?,
+            _ => return None,
+        }
+    }
+    Some(current)
+}
+
+/// Parses a human readable size like `10MB`, `1.5 GiB` or `512` into bytes.
+///
+/// SI units (`KB`, `MB`, ...) are powers of 1000, binary units (`KiB`, `MiB`, ...)
+/// powers of 1024. Units are case insensitive. Sizes that don't fit into a `u64` are
+/// invalid.
+fn parse_size(size: &str) -> Option<u64> {
+    const UNITS: [(&str, u64); 11] = [
+        ("b", 1),
+        ("kb", 1000),
+        ("mb", 1000_u64.pow(2)),
+        ("gb", 1000_u64.pow(3)),
+        ("tb", 1000_u64.pow(4)),
+        ("pb", 1000_u64.pow(5)),
+        ("kib", 1 << 10),
+        ("mib", 1 << 20),
+        ("gib", 1 << 30),
+        ("tib", 1 << 40),
+        ("pib", 1 << 50),
+    ];
+
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let unit = unit.trim().to_lowercase();
+
+    let multiplier = if unit.is_empty() {
+        1
+    } else {
+        UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, multiplier)| *multiplier)?
+    };
+
+    if let Ok(number) = number.parse::<u64>() {
+        return number.checked_mul(multiplier);
+    }
+
+    let number = number.parse::<f64>().ok()?;
+    // `u64::MAX` rounds up to 2^64, the first size that doesn't fit
+    #[expect(clippy::cast_precision_loss)]
+    let (bytes, too_large) = ((number * multiplier as f64).round(), u64::MAX as f64);
+    if !bytes.is_finite() || bytes >= too_large {
+        return None;
+    }
+
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(bytes as u64)
+}
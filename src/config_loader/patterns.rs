@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::{Error, config_meta::ConfigMeta};
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+/// Compiled patterns of the `regex` attributes, compiled on their first use
+static PATTERNS: OnceLock<Mutex<HashMap<&'static str, Regex>>> = OnceLock::new();
+
+impl ConfigLoader {
+    /// Checks the string values of fields with a `regex` attribute against their pattern.
+    ///
+    /// Arrays are checked item by item, other non string values are left to deserialization.
+    pub(super) fn validate_patterns<T: ConfigMeta>(
+        &self,
+        config: &Value,
+        provenance: &Provenance,
+    ) -> Result<(), Error> {
+        for field in T::config_metadata() {
+            let Some(pattern) = field.regex else {
+                continue;
+            };
+            let Some(value) = T::get_nested_value(config, &field.path) else {
+                continue;
+            };
+
+            let regex = compiled(pattern)
+                .map_err(|e| Error::Validation(format!("invalid regex for {}: {e}", field.path)))?;
+
+            let values = match value {
+                Value::Array(items) => items.iter().collect(),
+                value => vec![value],
+            };
+            for value in values {
+                if let Value::String(s) = value
+                    && !regex.is_match(s)
+                {
+                    return Err(Error::Validation(format!(
+                        "{} doesn't match the pattern `{pattern}`: {}",
+                        field.path,
+                        self.shown(&field.path, s, provenance)
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The compiled `pattern`, compiled once per process.
+fn compiled(pattern: &'static str) -> Result<Regex, regex::Error> {
+    let mut patterns = PATTERNS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let regex = match patterns.entry(pattern) {
+        Entry::Occupied(entry) => entry.get().clone(),
+        Entry::Vacant(entry) => entry.insert(Regex::new(pattern)?).clone(),
+    };
+    drop(patterns);
+
+    Ok(regex)
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, FileFormat, provenance::Provenance};
+use crate::{Error, config_meta::ConfigMeta, merge};
+use clap::{Arg, ArgAction, Command};
+use serde_json::Value;
+use std::{convert::Infallible, ffi::OsStr, process};
+
+/// Flag printing the effective config
+pub(super) const PRINT_CONFIG: &str = "print-config";
+/// Flag selecting the format of the printed config
+pub(super) const PRINT_CONFIG_FORMAT: &str = "print-config-format";
+
+/// Placeholder printed for required fields that no source sets
+const MISSING: &str = "<missing>";
+
+impl ConfigLoader {
+    /// If `--print-config` is among `args`.
+    pub(super) fn print_config_requested<A: AsRef<OsStr>>(&self, args: &[A]) -> bool {
+        let flag = format!("--{PRINT_CONFIG}");
+        self.print_config_flag && args.iter().any(|arg| arg.as_ref() == flag.as_str())
+    }
+
+    /// Adds the print config flags to `cmd` if they are enabled.
+    pub(super) fn with_print_config_args(&self, cmd: Command) -> Command {
+        if !self.print_config_flag {
+            return cmd;
+        }
+
+        cmd.arg(
+            Arg::new(PRINT_CONFIG)
+                .long(PRINT_CONFIG)
+                .action(ArgAction::SetTrue)
+                .help("Print the effective config and exit"),
+        )
+        .arg(
+            Arg::new(PRINT_CONFIG_FORMAT)
+                .long(PRINT_CONFIG_FORMAT)
+                .value_parser(["json", "yaml", "toml"])
+                .default_value("toml")
+                .help("Format of the printed config"),
+        )
+    }
+
+    /// Prints the merged `config` with redacted values, secrets and missing fields marked, then exits.
+    ///
+    /// Field precedences, migrations and defaults are applied like when loading, so the
+    /// printed values are the ones [`load`](Self::load) produces.
+    pub(super) fn print_config<T: ConfigMeta>(
+        &self,
+        mut config: Value,
+        provenance: &mut Provenance,
+        format: &str,
+    ) -> Result<Infallible, Error> {
+        let format = format.parse::<FileFormat>()?;
+
+        Self::apply_precedence::<T>(&mut config, provenance);
+        self.migrate(&mut config)?;
+        self.fill_defaults::<T>(&mut config, provenance);
+        for path in T::find_missing_required_fields(&config) {
+            let missing = merge::at_path(&path, Value::String(MISSING.to_string()));
+            config = merge::merge(config, missing);
+        }
+        // Secrets aren't resolved for printing, their references are hidden like the secrets
+        provenance.add_secret_paths(self.secret_paths(&config));
+        self.redact_paths(&mut config, provenance);
+
+        print!("{}", self.dump(&config, format)?);
+        process::exit(0)
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::{
+    Error, Source,
+    config_meta::{ConfigMeta, FieldType},
+    merge,
+};
+use serde_json::Value;
+use std::io::{self, BufRead, IsTerminal, Write};
+
+impl ConfigLoader {
+    /// Prompts for every missing required field and merges the answers at CLI priority.
+    ///
+    /// Does nothing unless prompts are enabled and stdin and stderr are terminals.
+    pub(super) fn merge_prompts<T: ConfigMeta>(
+        &self,
+        mut config: Value,
+        provenance: &mut Provenance,
+    ) -> Result<Value, Error> {
+        if !self.interactive_prompts || !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+            return Ok(config);
+        }
+
+        let mut missing = T::find_missing_required_fields(&config)
+            .into_iter()
+            .collect::<Vec<_>>();
+        missing.sort();
+
+        let metadata = T::config_metadata();
+        for path in missing {
+            let ty = metadata
+                .iter()
+                .find(|field| field.path == path)
+                .map_or(FieldType::Unknown, |field| field.ty.clone());
+
+            let prompt = format!("{path} ({ty}): ");
+            let answer = if self.redacted_paths.contains(&path) {
+                rpassword::prompt_password(prompt)?
+            } else {
+                let mut stderr = io::stderr();
+                stderr.write_all(prompt.as_bytes())?;
+                stderr.flush()?;
+
+                let mut answer = String::new();
+                io::stdin().lock().read_line(&mut answer)?;
+                answer
+            };
+
+            let answer = answer.trim_end_matches(['\r', '\n']);
+            if answer.is_empty() {
+                continue;
+            }
+
+            let value = match ty {
+                FieldType::Scalar("String") => Value::String(answer.to_string()),
+                _ => merge::parse_value(answer),
+            };
+            let value = merge::at_path(&path, value);
+            config = self.merge_json(config, value, Source::Cli, provenance);
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use crate::{Source, merge};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Tracks which source every leaf value in the merged config came from.
+#[derive(Debug, Default)]
+pub(super) struct Provenance {
+    sources: HashMap<String, Source>,
+    /// Everything each source provided, including values overridden later
+    layers: HashMap<Source, Value>,
+    /// Values at redacted paths of every source, to scrub from errors
+    redacted: Vec<String>,
+    /// Paths of values that were resolved from secrets
+    secret_paths: Vec<String>,
+}
+
+impl Provenance {
+    /// Adds `overlay` to everything provided by `source`.
+    pub(super) fn add_layer(&mut self, source: Source, overlay: &Value) {
+        let layer = self.layers.remove(&source).unwrap_or(Value::Null);
+        self.layers
+            .insert(source, merge::merge(layer, overlay.clone()));
+    }
+
+    /// Values at redacted paths of every merged source, including overridden values.
+    pub(super) fn redacted(&self) -> &[String] {
+        &self.redacted
+    }
+
+    pub(super) const fn redacted_mut(&mut self) -> &mut Vec<String> {
+        &mut self.redacted
+    }
+
+    /// Paths of values that were resolved from secrets, redacted like the redacted paths.
+    pub(super) fn secret_paths(&self) -> &[String] {
+        &self.secret_paths
+    }
+
+    /// Records the paths of values that were resolved from secrets.
+    pub(super) fn add_secret_paths(&mut self, paths: impl IntoIterator<Item = String>) {
+        self.secret_paths.extend(paths);
+    }
+
+    /// Everything `source` provided, including values overridden by other sources.
+    pub(super) fn layer(&self, source: Source) -> Option<&Value> {
+        self.layers.get(&source)
+    }
+
+    /// The source the value at `path` came from.
+    ///
+    /// For objects this is the source of all their values, `None` if they came from several.
+    pub(super) fn source(&self, path: &str) -> Option<Source> {
+        if let Some(source) = self.sources.get(path) {
+            return Some(*source);
+        }
+
+        let nested = format!("{path}.");
+        let mut sources = self
+            .sources
+            .iter()
+            .filter(|(key, _)| key.starts_with(&nested))
+            .map(|(_, source)| *source);
+        let first = sources.next()?;
+        sources.all(|source| source == first).then_some(first)
+    }
+
+    /// If more than one source provided a value at `path`.
+    pub(super) fn overridden(&self, path: &str) -> bool {
+        self.layers
+            .values()
+            .filter(|layer| {
+                path.split('.')
+                    .try_fold(*layer, |value, key| value.get(key))
+                    .is_some()
+            })
+            .count()
+            > 1
+    }
+
+    /// Records `value` as coming from `source`, replacing everything previously recorded at `path`.
+    pub(super) fn record(&mut self, path: &str, value: &Value, source: Source) {
+        self.remove(path);
+        self.record_leaves(path, value, source);
+    }
+
+    /// Forgets everything recorded at `path`, e.g. after the key was deleted.
+    pub(super) fn remove(&mut self, path: &str) {
+        let nested = format!("{path}.");
+        self.sources
+            .retain(|key, _| key != path && !key.starts_with(&nested));
+    }
+
+    fn record_leaves(&mut self, path: &str, value: &Value, source: Source) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, value) in map {
+                    self.record_leaves(&format!("{path}.{key}"), value, source);
+                }
+            }
+            _ => {
+                self.sources.insert(path.to_string(), source);
+            }
+        }
+    }
+
+    /// Returns the parts of `config` whose values originated from `source`.
+    pub(super) fn filter(&self, config: &Value, source: Source) -> Value {
+        let Value::Object(map) = config else {
+            return Value::Object(Map::new());
+        };
+
+        Value::Object(self.filter_map(map, "", source))
+    }
+
+    fn filter_map(
+        &self,
+        map: &Map<String, Value>,
+        prefix: &str,
+        source: Source,
+    ) -> Map<String, Value> {
+        let mut filtered = Map::new();
+
+        for (key, value) in map {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+
+            match value {
+                Value::Object(nested) if !nested.is_empty() => {
+                    let nested = self.filter_map(nested, &path, source);
+                    if !nested.is_empty() {
+                        filtered.insert(key.clone(), Value::Object(nested));
+                    }
+                }
+                _ => {
+                    if self.sources.get(&path) == Some(&source) {
+                        filtered.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        filtered
+    }
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::Error;
+use serde_json::Value;
+use std::{fmt::Debug, io};
+
+/// Replacement for redacted values in error messages
+const REDACTED: &str = "[REDACTED]";
+
+impl ConfigLoader {
+    /// Adds the values at the redacted paths of `config`, as they can appear in error
+    /// messages, to `values`.
+    ///
+    /// Only strings are collected, numbers and bools are too short to be told apart from
+    /// the rest of a message, e.g. a line number.
+    pub(super) fn collect_redacted(&self, config: &Value, values: &mut Vec<String>) {
+        if self.redacted_paths.is_empty() {
+            return;
+        }
+
+        for path in &self.redacted_paths {
+            if let Some(value) = path
+                .split('.')
+                .try_fold(config, |value, key| value.get(key))
+            {
+                collect_leaves(value, values);
+            }
+        }
+
+        // Longer values first, so values containing each other are fully scrubbed
+        values.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        values.dedup();
+    }
+
+    /// Adds the resolved secrets in `config` to `values`, like [`collect_redacted`](Self::collect_redacted)
+    /// does for the redacted paths.
+    pub(super) fn collect_secrets(
+        config: &Value,
+        provenance: &Provenance,
+        values: &mut Vec<String>,
+    ) {
+        for path in provenance.secret_paths() {
+            if let Some(value) = value_at(config, path) {
+                collect_leaves(value, values);
+            }
+        }
+
+        values.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        values.dedup();
+    }
+
+    /// Scrubs the redacted values of the sources merged so far from `error`.
+    pub(super) fn redact_sources(error: Error, provenance: &Provenance) -> Error {
+        Self::redact(error, provenance.redacted())
+    }
+
+    /// If the value at the dotted `path` is redacted, because it is at or below a redacted
+    /// path or the path of a resolved secret.
+    pub(super) fn is_redacted(&self, path: &str, provenance: &Provenance) -> bool {
+        self.redacted_paths
+            .iter()
+            .chain(provenance.secret_paths())
+            .any(|redacted| {
+                path.strip_prefix(redacted.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+    }
+
+    /// `value` as it is shown in an error about the field at `path`, the placeholder if
+    /// the path is redacted.
+    ///
+    /// Unlike scrubbing messages, this also hides numbers and bools.
+    pub(super) fn shown(&self, path: &str, value: &dyn Debug, provenance: &Provenance) -> String {
+        if self.is_redacted(path, provenance) {
+            REDACTED.to_string()
+        } else {
+            format!("{value:?}")
+        }
+    }
+
+    /// Replaces the values at the redacted paths and at the paths of resolved secrets with
+    /// a placeholder.
+    pub(super) fn redact_paths(&self, config: &mut Value, provenance: &Provenance) {
+        for path in self.redacted_paths.iter().chain(provenance.secret_paths()) {
+            if let Some(value) = value_at_mut(config, path) {
+                *value = Value::String(REDACTED.to_string());
+            }
+        }
+    }
+
+    /// Scrubs `values` from the message of `error`.
+    ///
+    /// Used for messages konfik doesn't know the path of, like the errors of validators
+    /// and custom sources. Values are only replaced where they aren't part of a longer
+    /// word, so a short value doesn't mangle the rest of the message. Errors of other
+    /// crates are only rebuilt from their message if it contains a value.
+    pub(super) fn redact(error: Error, values: &[String]) -> Error {
+        if values.is_empty() {
+            return error;
+        }
+
+        let scrub = |message: String| {
+            values
+                .iter()
+                .fold(message, |message, value| scrub_value(&message, value))
+        };
+        let scrubbed = |message: String| {
+            let scrubbed = scrub(message.clone());
+            (scrubbed != message).then_some(scrubbed)
+        };
+
+        match error {
+            Error::Io(e) => {
+                let kind = e.kind();
+                scrubbed(e.to_string()).map_or_else(
+                    || Error::Io(e),
+                    |message| Error::Io(io::Error::new(kind, message)),
+                )
+            }
+            Error::Serde(e) => scrubbed(e.to_string()).map_or_else(
+                || Error::Serde(e),
+                |message| Error::Serde(serde::de::Error::custom(message)),
+            ),
+            Error::Toml(e) => scrubbed(e.to_string()).map_or_else(
+                || Error::Toml(e),
+                |message| Error::Toml(serde::de::Error::custom(message)),
+            ),
+            Error::TomlSerialize(e) => scrubbed(e.to_string()).map_or_else(
+                || Error::TomlSerialize(e),
+                |message| Error::TomlSerialize(serde::ser::Error::custom(message)),
+            ),
+            Error::Yaml(e) => scrubbed(e.to_string())
+                .map_or_else(|| Error::Yaml(e), |message| Error::Yaml(message.into())),
+            Error::File {
+                path,
+                snippet,
+                span,
+                source,
+            } => {
+                // The span no longer fits a scrubbed snippet
+                let (snippet, span) =
+                    scrubbed(snippet.clone()).map_or((snippet, span), |scrubbed| (scrubbed, None));
+                Error::File {
+                    path,
+                    snippet,
+                    span,
+                    source: Box::new(Self::redact(*source, values)),
+                }
+            }
+            Error::InvalidFile { path, source } => Error::InvalidFile {
+                path,
+                source: Box::new(Self::redact(*source, values)),
+            },
+            Error::ConfigParse { type_name, source } => scrubbed(source.to_string()).map_or_else(
+                || Error::ConfigParse { type_name, source },
+                |message| Error::ConfigParse {
+                    type_name,
+                    source: serde::de::Error::custom(message),
+                },
+            ),
+            Error::Environment(message) => Error::Environment(scrub(message)),
+            Error::Validation(message) => Error::Validation(scrub(message)),
+            Error::MissingRequired(paths) => {
+                Error::MissingRequired(paths.into_iter().map(scrub).collect())
+            }
+            Error::Multiple(errors) => Error::Multiple(
+                errors
+                    .into_iter()
+                    .map(|error| Self::redact(error, values))
+                    .collect(),
+            ),
+            Error::Remote(message) => Error::Remote(scrub(message)),
+            error @ (Error::ParseFileFormat(_) | Error::PermissionDenied { .. }) => error,
+        }
+    }
+}
+
+/// Replaces the occurrences of `value` in `message` that aren't part of a longer word.
+fn scrub_value(message: &str, value: &str) -> String {
+    if value.is_empty() {
+        return message.to_string();
+    }
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    // Only edges of the value that are word characters need a boundary
+    let needs_start = is_word(value.chars().next());
+    let needs_end = is_word(value.chars().next_back());
+
+    let mut scrubbed = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find(value) {
+        let end = start + value.len();
+        let inside_word = (needs_start && is_word(rest[..start].chars().next_back()))
+            || (needs_end && is_word(rest[end..].chars().next()));
+
+        scrubbed.push_str(&rest[..start]);
+        scrubbed.push_str(if inside_word { value } else { REDACTED });
+        rest = &rest[end..];
+    }
+    scrubbed.push_str(rest);
+    scrubbed
+}
+
+fn collect_leaves(value: &Value, values: &mut Vec<String>) {
+    match value {
+        Value::String(s) if !s.is_empty() => values.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|item| collect_leaves(item, values)),
+        Value::Object(map) => map.values().for_each(|item| collect_leaves(item, values)),
+        _ => {}
+    }
+}
+
+/// The value at the dotted `path`, array items are addressed by their index.
+fn value_at<'a>(config: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(config, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        value => value.get(key),
+    })
+}
+
+/// Like [`value_at`], but mutable.
+pub(super) fn value_at_mut<'a>(config: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.').try_fold(config, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get_mut(i)),
+        value => value.get_mut(key),
+    })
+}
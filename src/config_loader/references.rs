@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigLoader;
+use crate::{Error, config_meta::ConfigMeta};
+use serde_json::Value;
+
+impl ConfigLoader {
+    /// Resolves `${path.to.field}` references against the merged config.
+    pub(super) fn resolve_references<T: ConfigMeta>(config: &mut Value) -> Result<(), Error> {
+        let root = config.clone();
+        resolve_value::<T>(config, &root, &mut Vec::new())
+    }
+}
+
+fn resolve_value<T: ConfigMeta>(
+    value: &mut Value,
+    root: &Value,
+    stack: &mut Vec<String>,
+) -> Result<(), Error> {
+    match value {
+        Value::String(s) if s.contains('$') => *value = resolve_str::<T>(s, root, stack)?,
+        Value::Array(items) => {
+            for item in items {
+                resolve_value::<T>(item, root, stack)?;
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                resolve_value::<T>(item, root, stack)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Resolves the references in `s`, a string that is a single reference keeps the type of its target.
+fn resolve_str<T: ConfigMeta>(
+    s: &str,
+    root: &Value,
+    stack: &mut Vec<String>,
+) -> Result<Value, Error> {
+    if let Some(path) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}'))
+        && !path.contains(['$', '{', '}'])
+    {
+        return resolve_path::<T>(path, root, stack);
+    }
+
+    let mut resolved = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('$') {
+        resolved.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("$${") {
+            // `$${` escapes a literal `${`
+            resolved.push_str("${");
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after
+                .find('}')
+                .ok_or_else(|| Error::Validation(format!("unterminated reference in {s:?}")))?;
+
+            match resolve_path::<T>(&after[..end], root, stack)? {
+                Value::String(value) => resolved.push_str(&value),
+                value => resolved.push_str(&value.to_string()),
+            }
+            rest = &after[end + 1..];
+        } else {
+            resolved.push('$');
+            rest = &rest[1..];
+        }
+    }
+    resolved.push_str(rest);
+
+    Ok(Value::String(resolved))
+}
+
+fn resolve_path<T: ConfigMeta>(
+    path: &str,
+    root: &Value,
+    stack: &mut Vec<String>,
+) -> Result<Value, Error> {
+    if stack.iter().any(|p| p == path) {
+        return Err(Error::Validation(format!(
+            "reference cycle: {} -> {path}",
+            stack.join(" -> ")
+        )));
+    }
+
+    let mut target = T::get_nested_value(root, path)
+        .ok_or_else(|| Error::Validation(format!("reference to unknown config key {path}")))?
+        .clone();
+
+    stack.push(path.to_string());
+    resolve_value::<T>(&mut target, root, stack)?;
+    stack.pop();
+
+    Ok(target)
+}
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, redact::value_at_mut};
+use crate::Error;
+use serde_json::Value;
+
+/// Prefix of strings referencing a secret by name
+const SECRET_SCHEME: &str = "secret://";
+
+impl ConfigLoader {
+    /// Replaces `secret://<name>` strings with the value of the secret resolver, and returns
+    /// the dotted paths of the replaced values.
+    pub(super) fn resolve_secrets(&self, config: &mut Value) -> Result<Vec<String>, Error> {
+        let Some(resolver) = &self.secret_resolver else {
+            return Ok(Vec::new());
+        };
+
+        let paths = self.secret_paths(config);
+        for path in &paths {
+            let Some(value) = value_at_mut(config, path) else {
+                continue;
+            };
+            let Some(name) = value.as_str().and_then(|s| s.strip_prefix(SECRET_SCHEME)) else {
+                continue;
+            };
+
+            let secret = resolver(name).ok_or_else(|| {
+                Error::Validation(format!("unresolved secret `{name}` at {path}"))
+            })?;
+            *value = Value::String(secret);
+        }
+
+        Ok(paths)
+    }
+
+    /// The dotted paths of the `secret://<name>` strings in `config`, array items are
+    /// addressed by their index.
+    ///
+    /// Empty if no secret resolver is set, the strings are plain values then.
+    pub(super) fn secret_paths(&self, config: &Value) -> Vec<String> {
+        let mut paths = Vec::new();
+        if self.secret_resolver.is_some() {
+            collect_secret_paths(config, "", &mut paths);
+        }
+        paths
+    }
+}
+
+fn collect_secret_paths(value: &Value, path: &str, paths: &mut Vec<String>) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match value {
+        Value::String(s) if s.starts_with(SECRET_SCHEME) => paths.push(path.to_string()),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_secret_paths(item, &child_path(&i.to_string()), paths);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                collect_secret_paths(item, &child_path(key), paths);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, provenance::Provenance};
+use crate::{Error, ImmutableMode, config_meta::ConfigMeta, config_source::ConfigStream, merge};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{fmt::Debug, marker::PhantomData};
+
+impl ConfigLoader {
+    /// Turns the updates of a [`ConfigStream`] into typed configs
+    ///
+    /// Every update runs the regular pipeline again: config files and custom sources
+    /// are re-read, the update is merged on top of the [`Priority::Normal`](crate::Priority::Normal)
+    /// ones, followed by the [`Priority::High`](crate::Priority::High) files and sources and
+    /// the environment. Then the result is
+    /// validated and deserialized. Errors are redacted like for [`load`](Self::load) and
+    /// yielded per update, the subscription continues with the next one.
+    pub fn subscribe<T, S>(&self, stream: S) -> Subscription<'_, T, S>
+    where
+        T: DeserializeOwned + ConfigMeta,
+        S: ConfigStream,
+    {
+        Subscription {
+            loader: self,
+            stream,
+            immutable: None,
+            config: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the configs produced from a [`ConfigStream`], see [`ConfigLoader::subscribe`]
+pub struct Subscription<'a, T, S> {
+    loader: &'a ConfigLoader,
+    stream: S,
+    /// Values at the immutable paths of the first config
+    immutable: Option<Vec<(String, Option<Value>)>>,
+    config: PhantomData<fn() -> T>,
+}
+
+impl<T, S: Debug> Debug for Subscription<'_, T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("loader", &self.loader)
+            .field("stream", &self.stream)
+            .field("immutable", &self.immutable)
+            .finish()
+    }
+}
+
+impl<T, S> Subscription<'_, T, S>
+where
+    T: DeserializeOwned + ConfigMeta,
+{
+    /// Checks the immutable fields of `config` against the first config, or restores them.
+    fn check_immutable(&self, config: &mut Value) -> Result<(), Error> {
+        let Some(originals) = &self.immutable else {
+            return Ok(());
+        };
+
+        for (path, original) in originals {
+            if T::get_nested_value(config, path) == original.as_ref() {
+                continue;
+            }
+
+            match self.loader.immutable_mode {
+                ImmutableMode::Reject => {
+                    return Err(Error::Validation(format!(
+                        "{path} can't change after the first load"
+                    )));
+                }
+                ImmutableMode::KeepOriginal => match original {
+                    Some(original) => {
+                        let original = merge::at_path(path, original.clone());
+                        *config = merge::merge(config.take(), original);
+                    }
+                    // Absent from the first config, so it stays absent
+                    None => remove_path(config, path),
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes the value at the dotted `path` from `config`, if there is one.
+fn remove_path(config: &mut Value, path: &str) {
+    let (parent, key) = path
+        .rsplit_once('.')
+        .map_or((None, path), |(parent, key)| (Some(parent), key));
+    let parent = parent
+        .into_iter()
+        .flat_map(|parent| parent.split('.'))
+        .try_fold(config, |value, key| value.get_mut(key));
+
+    if let Some(Value::Object(map)) = parent {
+        map.remove(key);
+    }
+}
+
+impl<T, S> Iterator for Subscription<'_, T, S>
+where
+    T: DeserializeOwned + ConfigMeta,
+    S: ConfigStream,
+{
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let update = self.stream.next_update()?;
+
+        Some(update.and_then(|update| {
+            let mut provenance = Provenance::default();
+            let mut config = self
+                .loader
+                .load_sources_with_update::<T>(Some(update), &mut provenance)?;
+            self.check_immutable(&mut config)?;
+
+            let immutable = self
+                .loader
+                .immutable_fields
+                .iter()
+                .map(|path| (path.clone(), T::get_nested_value(&config, path).cloned()))
+                .collect();
+            let config = self.loader.finish(config, &mut provenance)?;
+
+            self.immutable.get_or_insert(immutable);
+            Ok(config)
+        }))
+    }
+}
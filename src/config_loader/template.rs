@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, FileFormat};
+use crate::{
+    Error,
+    config_meta::{ConfigMeta, FieldMeta},
+};
+use serde::Serialize;
+use std::{fmt::Write, fs, path::Path};
+
+/// Parent keys of the current line, with their indentation
+type Scope = Vec<(usize, String)>;
+
+impl ConfigLoader {
+    /// Writes `T::default()` to `path` as a config template
+    ///
+    /// TOML and YAML templates annotate every field with a comment stating
+    /// whether it is required and its type. JSON has no comments, so JSON
+    /// templates only contain the default values.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, FileFormat, Konfik};
+    ///
+    /// #[derive(serde::Deserialize, serde::Serialize, Konfik, Default)]
+    /// struct AppConfig {
+    ///     database_url: String,
+    ///     port: Option<u16>,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_template.toml");
+    /// ConfigLoader::write_template::<AppConfig>(&path, FileFormat::Toml)?;
+    ///
+    /// let template = std::fs::read_to_string(&path)?;
+    /// assert!(template.contains("# required, String\ndatabase_url = \"\""));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if `T::default()` can't be serialized to `format` or writing the file fails.
+    pub fn write_template<T>(path: impl AsRef<Path>, format: FileFormat) -> Result<(), Error>
+    where
+        T: ConfigMeta + Default + Serialize,
+    {
+        let config = T::default();
+        let fields = T::config_metadata();
+
+        let content = match format {
+            FileFormat::Json => serde_json::to_string_pretty(&config)? + "\n",
+            FileFormat::Toml => {
+                Self::annotate(&toml::to_string_pretty(&config)?, &fields, toml_key)
+            }
+            FileFormat::Yaml => Self::annotate(&Self::serialize_yaml(&config)?, &fields, yaml_key),
+        };
+
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Adds a comment above every line that sets a field.
+    ///
+    /// `key` extracts the dotted path of the key set on a line, if any, and
+    /// keeps track of the current table or indentation in `scope`.
+    fn annotate(
+        content: &str,
+        fields: &[FieldMeta],
+        key: fn(&str, &mut Scope) -> Option<String>,
+    ) -> String {
+        let mut scope = Vec::new();
+        let mut annotated = String::with_capacity(content.len());
+
+        for line in content.lines() {
+            if let Some(path) = key(line, &mut scope)
+                && let Some(field) = fields.iter().find(|field| field.path == path)
+            {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let required = if field.required && !field.has_default {
+                    "required"
+                } else {
+                    "optional"
+                };
+                let _ = writeln!(annotated, "{indent}# {required}, {}", field.ty);
+            }
+
+            annotated.push_str(line);
+            annotated.push('\n');
+        }
+
+        annotated
+    }
+
+    /// Serializes YAML with the `serde_yml` backend.
+    #[cfg(feature = "yaml-serde_yml")]
+    pub(super) fn serialize_yaml<T: Serialize>(config: &T) -> Result<String, Error> {
+        serde_yml::to_string(config).map_err(|e| Error::Yaml(Box::new(e)))
+    }
+
+    /// Serializes YAML with the `serde_yaml` backend.
+    #[cfg(all(feature = "yaml-serde_yaml", not(feature = "yaml-serde_yml")))]
+    pub(super) fn serialize_yaml<T: Serialize>(config: &T) -> Result<String, Error> {
+        serde_yaml::to_string(config).map_err(|e| Error::Yaml(Box::new(e)))
+    }
+
+    /// Fails since no YAML backend is enabled.
+    #[cfg(not(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml")))]
+    pub(super) fn serialize_yaml<T: Serialize>(_config: &T) -> Result<String, Error> {
+        Err(Error::Yaml(
+            "no YAML backend enabled, enable the `yaml-serde_yaml` or `yaml-serde_yml` feature"
+                .into(),
+        ))
+    }
+}
+
+/// Path of the key set on a TOML line, `scope` holds the current table.
+fn toml_key(line: &str, scope: &mut Scope) -> Option<String> {
+    let line = line.trim();
+
+    if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        scope.clear();
+        scope.push((0, table.trim_matches(['[', ']']).to_string()));
+        return None;
+    }
+
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim().trim_matches('"');
+
+    Some(match scope.first() {
+        Some((_, table)) => format!("{table}.{key}"),
+        None => key.to_string(),
+    })
+}
+
+/// Path of the key set on a YAML line, `scope` holds the parent keys with their indentation.
+fn yaml_key(line: &str, scope: &mut Scope) -> Option<String> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with(['#', '-']) {
+        return None;
+    }
+
+    let indent = line.len() - trimmed.len();
+    let (key, _) = trimmed.split_once(':')?;
+
+    scope.retain(|(parent_indent, _)| *parent_indent < indent);
+    let path = scope
+        .iter()
+        .map(|(_, parent)| parent.as_str())
+        .chain([key])
+        .collect::<Vec<_>>()
+        .join(".");
+    scope.push((indent, key.to_string()));
+
+    Some(path)
+}
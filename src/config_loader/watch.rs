@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::{ConfigLoader, Subscription};
+use crate::{Error, config_meta::ConfigMeta, config_source::FileWatcher};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+impl ConfigLoader {
+    /// Loads the config again every time one of its files changes
+    ///
+    /// The config files, layers and prioritized files are watched. The returned
+    /// [`Subscription`] yields the current config first, then blocks until a file
+    /// changes and yields the reloaded one. Every reload runs the whole pipeline,
+    /// including custom sources, env vars and validators, and errors are redacted and
+    /// yielded per reload like for [`subscribe`](Self::subscribe). Changes within 100ms of each other cause a
+    /// single reload. Run it on its own thread to keep the app going in the meantime.
+    ///
+    /// ```rust
+    /// use konfik::{ConfigLoader, Konfik};
+    /// use std::{thread, time::Duration};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     workers: u8,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("konfik_watch.toml");
+    /// std::fs::write(&path, "workers = 2")?;
+    ///
+    /// let loader = ConfigLoader::default().with_config_file(&path);
+    /// let mut configs = loader.watch::<AppConfig>()?;
+    /// assert_eq!(configs.next().transpose()?.map(|config| config.workers), Some(2));
+    ///
+    /// let writer = thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(50));
+    ///     std::fs::write(&path, "workers = 8")
+    /// });
+    /// assert_eq!(configs.next().transpose()?.map(|config| config.workers), Some(8));
+    /// writer.join().expect("writer panicked")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the directory of a config file doesn't exist or can't be
+    /// watched.
+    pub fn watch<T>(&self) -> Result<Subscription<'_, T, FileWatcher>, Error>
+    where
+        T: DeserializeOwned + ConfigMeta,
+    {
+        let files = self.config_files.iter().map(|(path, _, _)| path);
+        let watcher = FileWatcher::new(files, Duration::from_millis(100))?;
+
+        Ok(self.subscribe(watcher))
+    }
+}
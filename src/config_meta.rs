@@ -3,14 +3,43 @@
 
 //! Enhanced config metadata with field requirement analysis.
 
+use crate::{Error, Source};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::{any::Any, collections::HashSet, fmt::Display};
 
 /// Metadata about configuration fields
 pub trait ConfigMeta {
     /// Gets the config metadata from the types of each field
     fn config_metadata() -> Vec<FieldMeta>;
 
+    /// If keys without a matching field are rejected (`#[konfik(deny_unknown_fields)]`)
+    #[must_use]
+    fn deny_unknown_fields() -> bool {
+        false
+    }
+
+    /// Moves the values of skipped fields from `previous` into `self`, including the
+    /// skipped fields of nested configs
+    ///
+    /// Skipped fields hold runtime state instead of config, see
+    /// [`ConfigLoader::reload`](crate::ConfigLoader::reload). The fields of `previous` are
+    /// left with the fresh values of `self`.
+    fn carry_skipped(&mut self, previous: &mut Self) {
+        let _ = previous;
+    }
+
+    /// The config as [`Any`], for the hooks of
+    /// [`ConfigLoader::with_post_load`](crate::ConfigLoader::with_post_load)
+    ///
+    /// The derive implements it for types without generic parameters, other types can't
+    /// have post load hooks.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    /// If [`as_any`](Self::as_any) returns the config, checked when a post load hook is added
+    const HAS_ANY: bool = false;
+
     /// Corrects the full path for every field
     #[must_use]
     fn correct_paths(fields: Vec<FieldMeta>, parent: &str) -> impl Iterator<Item = FieldMeta> {
@@ -21,6 +50,26 @@ pub trait ConfigMeta {
     }
 
     /// Finds the missing required fields
+    ///
+    /// Skipped fields are never required, since they don't come from the config:
+    ///
+    /// ```rust
+    /// use konfik::{Konfik, config_meta::ConfigMeta};
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     #[serde(skip)]
+    ///     connections: Vec<String>,
+    /// }
+    ///
+    /// let metadata = AppConfig::config_metadata();
+    /// let connections = metadata.iter().find(|field| field.name == "connections");
+    /// assert!(connections.is_some_and(|field| field.skip && !field.required));
+    ///
+    /// let missing = AppConfig::find_missing_required_fields(&serde_json::json!({}));
+    /// assert_eq!(missing.into_iter().collect::<Vec<_>>(), ["port"]);
+    /// ```
     #[must_use]
     fn find_missing_required_fields(config: &Value) -> HashSet<String> {
         let metadata = Self::config_metadata();
@@ -34,13 +83,116 @@ pub trait ConfigMeta {
             let existing = Self::get_nested_value(config, &field.path);
 
             if field.required && !field.has_default && existing.is_none_or(Value::is_null) {
-                missing.insert(field.path.to_string());
+                missing.insert(field.path.clone());
             }
         }
 
         missing
     }
 
+    /// Finds the keys of `config` that don't belong to any field
+    #[must_use]
+    fn find_unknown_fields(config: &Value) -> Vec<String> {
+        fn walk(value: &Value, prefix: &str, paths: &[String], unknown: &mut Vec<String>) {
+            let Value::Object(map) = value else {
+                return;
+            };
+
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+
+                if paths.contains(&path) {
+                    continue;
+                }
+
+                let nested = format!("{path}.");
+                if paths.iter().any(|field| field.starts_with(&nested)) {
+                    walk(value, &path, paths, unknown);
+                } else {
+                    unknown.push(path);
+                }
+            }
+        }
+
+        let paths = Self::config_metadata()
+            .into_iter()
+            .map(|field| field.path)
+            .collect::<Vec<_>>();
+
+        let mut unknown = Vec::new();
+        walk(config, "", &paths, &mut unknown);
+        unknown
+    }
+
+    /// Gets the config metadata as a tree, following the dotted paths of the fields
+    ///
+    /// The root node has an empty name and no field. Every field becomes a node named by
+    /// the last segment of its path, nested fields hold the fields of their type as children.
+    ///
+    /// ```rust
+    /// use konfik::{Konfik, Nested, config_meta::ConfigMeta};
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Pool {
+    ///     size: u32,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Nested)]
+    /// struct Database {
+    ///     url: String,
+    ///     #[konfik(nested)]
+    ///     pool: Pool,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, Konfik)]
+    /// struct AppConfig {
+    ///     port: u16,
+    ///     #[konfik(nested)]
+    ///     database: Database,
+    /// }
+    ///
+    /// let tree = AppConfig::config_tree();
+    /// let names = |node: &konfik::config_meta::ConfigNode| {
+    ///     node.children.iter().map(|child| child.name.clone()).collect::<Vec<_>>()
+    /// };
+    ///
+    /// assert_eq!(names(&tree), ["port", "database"]);
+    /// assert_eq!(names(&tree.children[1]), ["url", "pool"]);
+    /// assert_eq!(names(&tree.children[1].children[1]), ["size"]);
+    ///
+    /// let size = tree.get("database.pool.size").and_then(|node| node.field.as_ref());
+    /// assert_eq!(size.map(|field| field.path.as_str()), Some("database.pool.size"));
+    /// ```
+    #[must_use]
+    fn config_tree() -> ConfigNode {
+        let mut root = ConfigNode::default();
+
+        for field in Self::config_metadata() {
+            let mut node = &mut root;
+            for name in field.path.split('.') {
+                let index = node
+                    .children
+                    .iter()
+                    .position(|child| child.name == name)
+                    .unwrap_or_else(|| {
+                        node.children.push(ConfigNode {
+                            name: name.to_string(),
+                            ..ConfigNode::default()
+                        });
+                        node.children.len() - 1
+                    });
+                node = &mut node.children[index];
+            }
+            node.field = Some(field);
+        }
+
+        root
+    }
+
     /// Gets the nested values of a JSON `Value`
     #[must_use]
     fn get_nested_value<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
@@ -55,16 +207,38 @@ pub trait ConfigMeta {
     }
 }
 
+/// A node of the [config tree](ConfigMeta::config_tree)
+#[derive(Debug, Clone, Default)]
+pub struct ConfigNode {
+    /// Last segment of the path, empty for the root
+    pub name: String,
+    /// Metadata of the field at this node, `None` for the root
+    pub field: Option<FieldMeta>,
+    /// Nodes of the fields below this one, in declaration order
+    pub children: Vec<Self>,
+}
+
+impl ConfigNode {
+    /// Gets the node at the dotted `path` below this one
+    #[must_use]
+    pub fn get(&self, path: &str) -> Option<&Self> {
+        path.split('.').try_fold(self, |node, name| {
+            node.children.iter().find(|child| child.name == name)
+        })
+    }
+}
+
 /// Field metadata with enhanced requirement detection
 #[expect(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct FieldMeta {
     /// Name of the field
     pub name: &'static str,
     /// Path to the field
     pub path: String,
     /// Type of the field
-    pub ty: &'static str,
+    pub ty: FieldType,
     /// If the field is required (non-optional)
     pub required: bool,
     /// If the field has `#[serde(skip)]`
@@ -73,4 +247,129 @@ pub struct FieldMeta {
     pub has_default: bool,
     /// If it's a nested type
     pub nested: bool,
+    /// If the field is a positional CLI argument (`#[konfik(positional)]` or `#[arg(index = N)]`)
+    pub positional: bool,
+    /// If the field holds the CLI subcommand (`#[command(subcommand)]`)
+    pub subcommand: bool,
+    /// If the field is left out of the CLI but still loaded from files and env (`#[konfik(cli_skip)]`)
+    pub cli_skip: bool,
+    /// If the field is a byte size that accepts units like `10MB` (`#[konfik(size)]`)
+    pub size: bool,
+    /// Old names of the field that still load but are deprecated (`#[konfik(deprecated_alias = "old")]`)
+    pub deprecated_aliases: &'static [&'static str],
+    /// Value an absent `Option` field gets, parsed like an env value (`#[konfik(default_if_none = "8080")]`)
+    pub default_if_none: Option<&'static str>,
+    /// If an explicit `null` also gets the `default_if_none` value instead of meaning `None` (`#[konfik(null_uses_default)]`)
+    pub null_uses_default: bool,
+    /// Env vars read instead of the derived name, the first one that is set wins (`#[konfik(env = "DATABASE_URL")]`)
+    ///
+    /// The names get the env prefix, unless they are absolute and start with `=`.
+    pub env: &'static [&'static str],
+    /// Long CLI flag used instead of the field name (`#[konfik(cli = "pg-dsn")]`)
+    pub cli: Option<&'static str>,
+    /// Function applied to the merged value of the field before validation (`#[konfik(transform = "path::to::fn")]`)
+    pub transform: Option<Transform>,
+    /// Source whose value always wins for this field, regardless of the global order (`#[konfik(precedence = "file")]`)
+    pub precedence: Option<Source>,
+    /// Pattern string values of the field must match, needs the `regex` feature (`#[konfik(regex = "^v\\d+$")]`)
+    pub regex: Option<&'static str>,
+    /// The serialized result of the field's default function, filled in when no source sets the field (`#[konfik(eval_default)]`)
+    pub default_fn: Option<DefaultFn>,
+}
+
+impl FieldMeta {
+    /// Metadata of an optional top level field without any attributes
+    ///
+    /// The other properties are set by assigning their fields.
+    ///
+    /// ```rust
+    /// use konfik::config_meta::{FieldMeta, FieldType};
+    ///
+    /// let mut field = FieldMeta::new("port", FieldType::Scalar("u16"));
+    /// field.required = true;
+    /// field.env = &["PORT"];
+    /// assert_eq!(field.path, "port");
+    /// ```
+    #[must_use]
+    pub fn new(name: &'static str, ty: FieldType) -> Self {
+        Self {
+            name,
+            path: name.to_string(),
+            ty,
+            required: false,
+            skip: false,
+            has_default: false,
+            nested: false,
+            positional: false,
+            subcommand: false,
+            cli_skip: false,
+            size: false,
+            deprecated_aliases: &[],
+            default_if_none: None,
+            null_uses_default: false,
+            env: &[],
+            cli: None,
+            transform: None,
+            precedence: None,
+            regex: None,
+            default_fn: None,
+        }
+    }
+}
+
+/// Function producing the serialized default value of a single field
+pub type DefaultFn = fn() -> Result<Value, Error>;
+
+/// Serializes the result of a default function, used by the [`DefaultFn`]s of the derive
+///
+/// # Errors
+///
+/// Returns an `Error` if `value` can't be serialized.
+pub fn serialize_default<V: serde::Serialize>(value: V) -> Result<Value, Error> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Function transforming the merged value of a single field
+pub type Transform = fn(Value) -> Result<Value, Error>;
+
+/// Structured description of a field's type
+///
+/// ```rust
+/// use konfik::{Konfik, config_meta::{ConfigMeta, FieldType}};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     ports: Option<Vec<u16>>,
+/// }
+///
+/// let ty = FieldType::Option(Box::new(FieldType::Vec(Box::new(FieldType::Scalar("u16")))));
+/// assert_eq!(AppConfig::config_metadata()[0].ty, ty);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    /// Any other type, named by the last segment of its path (`u16`, `String`, `PathBuf`)
+    Scalar(&'static str),
+    /// `Option<T>`
+    Option(Box<Self>),
+    /// `Vec<T>` or another sequence (`VecDeque`, `HashSet`, `BTreeSet`)
+    Vec(Box<Self>),
+    /// `HashMap` or `BTreeMap`
+    Map,
+    /// A nested config type (`#[konfik(nested)]` or `#[command(flatten)]`)
+    Nested,
+    /// A type that can't be described, e.g. tuples and arrays
+    Unknown,
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scalar(name) => write!(f, "{name}"),
+            Self::Option(inner) => write!(f, "Option<{inner}>"),
+            Self::Vec(inner) => write!(f, "Vec<{inner}>"),
+            Self::Map => write!(f, "Map"),
+            Self::Nested => write!(f, "Nested"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
 }
@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Explanation of how a configuration was resolved.
+
+use crate::Source;
+use serde_json::Value;
+use std::fmt::{self, Display};
+
+/// How every field of a configuration was resolved, see [`ConfigLoader::doctor`](crate::ConfigLoader::doctor)
+///
+/// The [`Display`] impl prints the fields as a table followed by the warnings.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    /// One entry per field, in declaration order
+    pub fields: Vec<FieldReport>,
+    /// Errors of loading and validating the configuration
+    pub warnings: Vec<String>,
+}
+
+/// How a single field was resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldReport {
+    /// Path to the field
+    pub path: String,
+    /// The final value, `None` if no source provided one. Redacted paths show `[REDACTED]`
+    pub value: Option<Value>,
+    /// The source of the final value
+    pub source: Option<Source>,
+    /// If the value is a default instead of coming from a source
+    pub default: bool,
+    /// If more than one source provided a value, so the final one overrode the others
+    pub overridden: bool,
+    /// Problems of this field, e.g. a missing required value
+    pub warnings: Vec<String>,
+}
+
+impl FieldReport {
+    /// Short description of how the value was resolved
+    const fn status(&self) -> &'static str {
+        if !self.warnings.is_empty() {
+            "warning"
+        } else if self.default {
+            "default"
+        } else if self.overridden {
+            "overridden"
+        } else if self.value.is_some() {
+            "set"
+        } else {
+            "unset"
+        }
+    }
+}
+
+impl ConfigReport {
+    /// Gets the report of the field at `path`
+    #[must_use]
+    pub fn field(&self, path: &str) -> Option<&FieldReport> {
+        self.fields.iter().find(|field| field.path == path)
+    }
+
+    /// If no field or validator reported a problem
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.warnings.is_empty() && self.fields.iter().all(|field| field.warnings.is_empty())
+    }
+}
+
+impl Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HEADER: [&str; 4] = ["FIELD", "VALUE", "SOURCE", "STATUS"];
+
+        let rows = self
+            .fields
+            .iter()
+            .map(|field| {
+                [
+                    field.path.clone(),
+                    field
+                        .value
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), Value::to_string),
+                    field
+                        .source
+                        .map_or_else(|| "-".to_string(), |source| source.to_string()),
+                    field.status().to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = HEADER.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let write_row = |f: &mut fmt::Formatter<'_>, row: [&str; 4]| {
+            let [path, value, source, status] = row;
+            let [path_width, value_width, source_width, _] = widths;
+            writeln!(
+                f,
+                "{path:path_width$}  {value:value_width$}  {source:source_width$}  {status}"
+            )
+        };
+
+        write_row(f, HEADER)?;
+        for row in &rows {
+            write_row(f, row.each_ref().map(String::as_str))?;
+        }
+
+        let field_warnings = self.fields.iter().flat_map(|field| {
+            field
+                .warnings
+                .iter()
+                .map(move |warning| format!("{}: {warning}", field.path))
+        });
+        let warnings = field_warnings
+            .chain(self.warnings.iter().cloned())
+            .collect::<Vec<_>>();
+        if !warnings.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "warnings:")?;
+            for warning in warnings {
+                writeln!(f, "  - {warning}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Extension points for configuration sources beyond files, env vars and CLI args.
+
+#[cfg(feature = "cloud")]
+pub mod cloud;
+#[cfg(feature = "watch")]
+mod file_watcher;
+#[cfg(any(feature = "cloud", feature = "vault"))]
+mod secret_cli;
+#[cfg(unix)]
+mod unix_socket;
+#[cfg(feature = "vault")]
+mod vault;
+
+#[cfg(feature = "watch")]
+pub use file_watcher::FileWatcher;
+#[cfg(unix)]
+pub use unix_socket::UnixSocketSource;
+#[cfg(feature = "vault")]
+pub use vault::VaultSecret;
+
+use crate::{Error, config_meta::FieldMeta};
+use serde_json::Value;
+use std::sync::mpsc::Receiver;
+
+/// A source that provides configuration on demand
+pub trait ConfigSource {
+    /// Loads the configuration values of this source
+    ///
+    /// `fields` is the metadata of the type being loaded, for sources that look
+    /// up values per field. Sources without any values return an empty object.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if the source can't be read.
+    fn load(&self, fields: &[FieldMeta]) -> Result<Value, Error>;
+}
+
+/// A source that pushes configuration updates, e.g. over a network stream
+///
+/// Every update is a complete configuration document. Use
+/// [`ConfigLoader::subscribe`](crate::ConfigLoader::subscribe) to turn the
+/// updates into typed configs.
+pub trait ConfigStream {
+    /// Blocks until the next update arrives, returns `None` once the stream has ended
+    fn next_update(&mut self) -> Option<Result<Value, Error>>;
+}
+
+/// [`ConfigStream`] receiving its updates over a channel
+///
+/// ```rust
+/// use konfik::{ConfigLoader, Konfik, config_source::ChannelSource};
+/// use serde_json::json;
+/// use std::sync::mpsc;
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     port: u16,
+/// }
+///
+/// let (sender, receiver) = mpsc::channel();
+/// sender.send(json!({ "port": 80 }))?;
+/// sender.send(json!({ "port": 8080 }))?;
+/// drop(sender);
+///
+/// let loader = ConfigLoader::default();
+/// let ports = loader
+///     .subscribe::<AppConfig, _>(ChannelSource::new(receiver))
+///     .map(|config| config.map(|config| config.port))
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(ports, [80, 8080]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct ChannelSource {
+    receiver: Receiver<Value>,
+}
+
+impl ChannelSource {
+    /// Creates a stream from the receiving half of a channel
+    #[must_use]
+    pub const fn new(receiver: Receiver<Value>) -> Self {
+        Self { receiver }
+    }
+}
+
+impl ConfigStream for ChannelSource {
+    fn next_update(&mut self) -> Option<Result<Value, Error>> {
+        self.receiver.recv().ok().map(Ok)
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Sources reading a JSON secret from a cloud secret manager.
+//!
+//! Both sources call the official CLI (`aws` or `gcloud`), so they use the credentials,
+//! profiles and regions the CLI is configured with and don't pull in a cloud SDK. The
+//! secret has to be a JSON object, it is merged like a config file with [`ConfigLoader::with_source`](crate::ConfigLoader::with_source).
+
+use super::{ConfigSource, secret_cli::read_secret};
+use crate::{Error, config_meta::FieldMeta};
+use serde_json::Value;
+use std::process::Command;
+
+/// Reads a JSON secret from AWS Secrets Manager with `aws secretsmanager get-secret-value`
+///
+/// ```rust,no_run
+/// use konfik::{ConfigLoader, Konfik, config_source::cloud::AwsSecret};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     database_url: String,
+/// }
+///
+/// let config = ConfigLoader::default()
+///     .with_source(AwsSecret::new("arn:aws:secretsmanager:eu-west-1:123456789012:secret:myapp").region("eu-west-1"))
+///     .load::<AppConfig>()?;
+/// # Ok::<(), konfik::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct AwsSecret {
+    secret_id: String,
+    region: Option<String>,
+}
+
+impl AwsSecret {
+    /// Reads the secret with this name or ARN
+    #[must_use]
+    pub fn new(secret_id: impl Into<String>) -> Self {
+        Self {
+            secret_id: secret_id.into(),
+            region: None,
+        }
+    }
+
+    /// Use this region instead of the one configured for the CLI
+    #[must_use]
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+}
+
+impl ConfigSource for AwsSecret {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        let mut command = Command::new("aws");
+        command.args([
+            "secretsmanager",
+            "get-secret-value",
+            "--secret-id",
+            &self.secret_id,
+            "--query",
+            "SecretString",
+            "--output",
+            "text",
+        ]);
+        if let Some(region) = &self.region {
+            command.args(["--region", region]);
+        }
+
+        read_secret(command, &self.secret_id)
+    }
+}
+
+/// Reads a JSON secret from GCP Secret Manager with `gcloud secrets versions access`
+///
+/// ```rust,no_run
+/// use konfik::{ConfigLoader, Konfik, config_source::cloud::GcpSecret};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     database_url: String,
+/// }
+///
+/// let config = ConfigLoader::default()
+///     .with_source(GcpSecret::new("myapp-config").project("my-project"))
+///     .load::<AppConfig>()?;
+/// # Ok::<(), konfik::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GcpSecret {
+    name: String,
+    version: String,
+    project: Option<String>,
+}
+
+impl GcpSecret {
+    /// Reads the latest version of the secret with this name
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: "latest".to_string(),
+            project: None,
+        }
+    }
+
+    /// Read this version instead of the latest one
+    #[must_use]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Use this project instead of the one configured for the CLI
+    #[must_use]
+    pub fn project(mut self, project: impl Into<String>) -> Self {
+        self.project = Some(project.into());
+        self
+    }
+}
+
+impl ConfigSource for GcpSecret {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        let mut command = Command::new("gcloud");
+        command.args([
+            "secrets",
+            "versions",
+            "access",
+            &self.version,
+            "--secret",
+            &self.name,
+        ]);
+        if let Some(project) = &self.project {
+            command.args(["--project", project]);
+        }
+
+        read_secret(command, &self.name)
+    }
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigStream;
+use crate::Error;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+/// [`ConfigStream`] signaling changes of config files, see [`ConfigLoader::watch`](crate::ConfigLoader::watch)
+///
+/// The first update is sent right away, every later one after a watched file was
+/// created, changed or removed. The updates are empty objects, so the configs of a
+/// subscription come from the files alone. Files are watched through their directory,
+/// which has to exist, so files that are created later or replaced by editors are
+/// picked up too.
+#[derive(Debug)]
+pub struct FileWatcher {
+    /// Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    files: HashSet<PathBuf>,
+    debounce: Duration,
+    started: bool,
+}
+
+impl FileWatcher {
+    /// Watches `files`, waiting `debounce` for more events after a change
+    ///
+    /// Editors often write a file in several steps, each of them sends an event.
+    /// Events arriving within `debounce` of each other cause a single update.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the directory of a file doesn't exist or can't be watched.
+    pub fn new<P: AsRef<Path>>(
+        files: impl IntoIterator<Item = P>,
+        debounce: Duration,
+    ) -> Result<Self, Error> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(io::Error::other)?;
+
+        let mut dirs = HashSet::new();
+        let mut paths = HashSet::new();
+        for file in files {
+            let file = file.as_ref();
+            let dir = match file.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            let dir = dir.canonicalize()?;
+            if let Some(name) = file.file_name() {
+                paths.insert(dir.join(name));
+            }
+            dirs.insert(dir);
+        }
+
+        for dir in &dirs {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(io::Error::other)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            files: paths,
+            debounce,
+            started: false,
+        })
+    }
+
+    /// If `event` changed one of the watched files
+    fn is_change(&self, event: &notify::Event) -> bool {
+        !matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|path| self.files.contains(path))
+    }
+}
+
+impl ConfigStream for FileWatcher {
+    fn next_update(&mut self) -> Option<Result<Value, Error>> {
+        if !self.started {
+            self.started = true;
+            return Some(Ok(Value::Object(Map::new())));
+        }
+
+        loop {
+            match self.events.recv().ok()? {
+                Ok(event) if self.is_change(&event) => break,
+                Ok(_) => {}
+                Err(e) => return Some(Err(Error::Io(io::Error::other(e)))),
+            }
+        }
+        while self.events.recv_timeout(self.debounce).is_ok() {}
+
+        Some(Ok(Value::Object(Map::new())))
+    }
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use crate::Error;
+use serde_json::Value;
+use std::process::Command;
+
+/// Runs the CLI command and parses its output as a JSON object.
+pub(super) fn read_secret(mut command: Command, secret: &str) -> Result<Value, Error> {
+    let output = command
+        .output()
+        .map_err(|e| Error::Remote(format!("{secret}: failed to run the CLI: {e}")))?;
+
+    if !output.status.success() {
+        return Err(Error::Remote(format!(
+            "{secret}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    match serde_json::from_slice(&output.stdout) {
+        Ok(value @ Value::Object(_)) => Ok(value),
+        Ok(_) => Err(Error::Remote(format!(
+            "{secret}: the secret is not a JSON object"
+        ))),
+        Err(e) => Err(Error::Remote(format!(
+            "{secret}: the secret is not valid JSON: {e}"
+        ))),
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use super::ConfigSource;
+use crate::{Error, config_meta::FieldMeta};
+use serde_json::Value;
+use std::{
+    io::Read,
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// [`ConfigSource`] reading a JSON object from a Unix domain socket, e.g. a local config agent
+///
+/// Every load connects to the socket and reads until the agent closes the connection.
+/// Connection, read and parse errors are reported as [`Error::Remote`].
+///
+/// ```rust
+/// use konfik::{ConfigLoader, Konfik, config_source::UnixSocketSource};
+/// use std::{io::Write, os::unix::net::UnixListener};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     port: u16,
+/// }
+///
+/// let path = std::env::temp_dir().join("konfik_unix_socket.sock");
+/// let _ = std::fs::remove_file(&path);
+/// let listener = UnixListener::bind(&path)?;
+/// let agent = std::thread::spawn(move || -> std::io::Result<()> {
+///     let (mut stream, _) = listener.accept()?;
+///     stream.write_all(br#"{ "port": 8080 }"#)
+/// });
+///
+/// let config = ConfigLoader::default()
+///     .with_source(UnixSocketSource::new(&path))
+///     .load::<AppConfig>()?;
+///
+/// assert_eq!(config.port, 8080);
+/// agent.join().expect("agent panicked")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnixSocketSource {
+    path: PathBuf,
+    timeout: Option<Duration>,
+}
+
+impl UnixSocketSource {
+    /// Reads the config from the socket at `path`
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            timeout: None,
+        }
+    }
+
+    /// Fail if the agent doesn't respond within `timeout`, instead of waiting forever
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl ConfigSource for UnixSocketSource {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        let remote =
+            |e: &dyn std::fmt::Display| Error::Remote(format!("{}: {e}", self.path.display()));
+
+        let mut stream = UnixStream::connect(&self.path).map_err(|e| remote(&e))?;
+        stream
+            .set_read_timeout(self.timeout)
+            .map_err(|e| remote(&e))?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(|e| remote(&e))?;
+
+        match serde_json::from_slice(&response) {
+            Ok(value @ Value::Object(_)) => Ok(value),
+            Ok(_) => Err(remote(&"the response is not a JSON object")),
+            Err(e) => Err(remote(&format!("the response is not valid JSON: {e}"))),
+        }
+    }
+}
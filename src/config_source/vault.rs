@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Source reading a secret from Vault.
+
+use super::{ConfigSource, secret_cli::read_secret};
+use crate::{Error, config_meta::FieldMeta};
+use serde_json::Value;
+use std::process::Command;
+
+/// Reads a secret from Vault with `vault read`
+///
+/// Like the [cloud sources](super::cloud), it calls the CLI, so it uses the `VAULT_ADDR`,
+/// `VAULT_TOKEN` and other settings the CLI is configured with. The path is the API path,
+/// e.g. `secret/data/myapp` for version 2 of the KV engine; the values are unwrapped from
+/// the secret's metadata. See [`ConfigLoader::with_vault`](crate::ConfigLoader::with_vault).
+///
+/// ```rust,no_run
+/// use konfik::{ConfigLoader, Konfik, config_source::VaultSecret};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     database_url: String,
+/// }
+///
+/// let config = ConfigLoader::default()
+///     .with_source(VaultSecret::new("secret/data/myapp").address("https://vault.internal:8200"))
+///     .load::<AppConfig>()?;
+/// # Ok::<(), konfik::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct VaultSecret {
+    path: String,
+    address: Option<String>,
+}
+
+impl VaultSecret {
+    /// Reads the secret at this API path
+    #[must_use]
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            address: None,
+        }
+    }
+
+    /// Use this Vault server instead of the one configured for the CLI
+    #[must_use]
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.address = Some(address.into());
+        self
+    }
+}
+
+impl ConfigSource for VaultSecret {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        let mut command = Command::new("vault");
+        command.args(["read", "-format=json"]);
+        if let Some(address) = &self.address {
+            command.arg(format!("-address={address}"));
+        }
+        command.arg(&self.path);
+
+        let response = read_secret(command, &self.path)?;
+        secret_data(response)
+            .ok_or_else(|| Error::Remote(format!("{}: the secret has no data", self.path)))
+    }
+}
+
+/// The values of the secret in a `vault read` response, for both versions of the KV engine.
+fn secret_data(mut response: Value) -> Option<Value> {
+    match response.get_mut("data").map(Value::take) {
+        // KV version 2 wraps the values together with their metadata, the values of
+        // deleted versions are null
+        Some(Value::Object(mut data))
+            if data.contains_key("metadata") && data.contains_key("data") =>
+        {
+            data.remove("data").filter(Value::is_object)
+        }
+        Some(data @ Value::Object(_)) => Some(data),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::secret_data;
+    use serde_json::json;
+
+    #[test]
+    fn kv_v1_values_are_the_data() {
+        let response = json!({
+            "lease_duration": 2_764_800,
+            "data": { "database_url": "postgres://db", "metadata": "kept" },
+        });
+
+        assert_eq!(
+            secret_data(response),
+            Some(json!({ "database_url": "postgres://db", "metadata": "kept" }))
+        );
+    }
+
+    #[test]
+    fn kv_v2_values_are_unwrapped_from_the_metadata() {
+        let response = json!({
+            "data": {
+                "data": { "database_url": "postgres://db" },
+                "metadata": { "version": 3 },
+            },
+        });
+
+        assert_eq!(
+            secret_data(response),
+            Some(json!({ "database_url": "postgres://db" }))
+        );
+    }
+
+    #[test]
+    fn responses_without_data_have_no_values() {
+        assert_eq!(secret_data(json!({ "warnings": null })), None);
+        assert_eq!(secret_data(json!({ "data": null })), None);
+    }
+
+    #[test]
+    fn deleted_kv_v2_versions_have_no_values() {
+        let response = json!({
+            "data": {
+                "data": null,
+                "metadata": { "version": 3, "deletion_time": "2025-01-01T00:00:00Z" },
+            },
+        });
+
+        assert_eq!(secret_data(response), None);
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use crate::ConfigLoader;
+use std::sync::{PoisonError, RwLock};
+
+static DEFAULT_LOADER: RwLock<Option<ConfigLoader>> = RwLock::new(None);
+
+/// Sets the loader used by the derived [`LoadConfig::load`](crate::LoadConfig::load)
+///
+/// Configure the prefix, files and validators once at startup instead of passing a
+/// loader through the whole app. Every later `T::load()` uses a clone of `loader`, on any
+/// thread. Loads that started before the call still use [`ConfigLoader::default`], so set
+/// it before the first `load()`. Calling it again replaces the previous loader.
+///
+/// ```rust,standalone_crate
+/// use konfik::{ConfigLoader, Konfik, LoadConfig};
+///
+/// #[derive(serde::Deserialize, Konfik)]
+/// struct AppConfig {
+///     port: u16,
+/// }
+///
+/// // SAFETY: nothing else touches the environment in this example
+/// unsafe { std::env::set_var("GLOBAL_PORT", "8080") };
+///
+/// konfik::set_default_loader(ConfigLoader::default().with_env_prefix("GLOBAL"));
+///
+/// let config = std::thread::spawn(AppConfig::load).join().unwrap()?;
+/// assert_eq!(config.port, 8080);
+/// # Ok::<(), konfik::Error>(())
+/// ```
+pub fn set_default_loader(loader: ConfigLoader) {
+    *DEFAULT_LOADER
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(loader);
+}
+
+/// Returns a clone of the loader set with [`set_default_loader`], or [`ConfigLoader::default`]
+#[must_use]
+pub fn default_loader() -> ConfigLoader {
+    DEFAULT_LOADER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or_default()
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use crate::Error;
+use serde::{Deserialize, Deserializer, de::DeserializeOwned};
+use serde_json::Value;
+
+/// Deserializes the subtree of `value` at the dotted `path` into `T`.
+///
+/// An empty path deserializes the whole value. A missing subtree is deserialized from
+/// `null`, so `Option` targets become `None`.
+///
+/// ```rust
+/// use serde_json::json;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Cache {
+///     size: u32,
+/// }
+///
+/// let merged = json!({ "plugins": { "cache": { "size": 64 } } });
+///
+/// let cache = konfik::deserialize_at::<Cache>(&merged, "plugins.cache")?;
+/// let metrics = konfik::deserialize_at::<Option<Cache>>(&merged, "plugins.metrics")?;
+///
+/// assert_eq!(cache.size, 64);
+/// assert!(metrics.is_none());
+/// # Ok::<(), konfik::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::ConfigParse`] if the subtree can't be deserialized into `T`.
+pub fn deserialize_at<T: DeserializeOwned>(value: &Value, path: &str) -> Result<T, Error> {
+    let mut current = Some(value);
+    if !path.is_empty() {
+        for key in path.split('.') {
+            current = current.and_then(|v| v.get(key));
+        }
+    }
+
+    T::deserialize(current.unwrap_or(&Value::Null)).map_err(|e| Error::ConfigParse {
+        type_name: std::any::type_name::<T>(),
+        source: e,
+    })
+}
+
+/// Deserializes an `Option<Option<T>>` field, keeping an explicit `null` apart from an absent key
+///
+/// Use it with `#[serde(default, deserialize_with = "konfik::explicit_null")]`. An absent key
+/// becomes `None`, an explicit `null` `Some(None)` and any other value `Some(Some(value))`.
+/// Plain `Option<Option<T>>` fields turn both `null` and absent keys into `None`.
+///
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     #[serde(default, deserialize_with = "konfik::explicit_null")]
+///     proxy: Option<Option<String>>,
+/// }
+///
+/// let proxy = |json| Ok::<_, serde_json::Error>(serde_json::from_str::<Config>(json)?.proxy);
+///
+/// assert_eq!(proxy("{}")?, None);
+/// assert_eq!(proxy(r#"{ "proxy": null }"#)?, Some(None));
+/// assert_eq!(proxy(r#"{ "proxy": "socks5://proxy" }"#)?, Some(Some("socks5://proxy".into())));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of deserializing `T`.
+pub fn explicit_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! [`miette::Diagnostic`] for [`Error`], enabled with the `miette` feature.
+//!
+//! Parse errors of TOML and JSON files point at the offending location:
+//!
+//! ```rust
+//! use konfik::{ConfigLoader, Konfik};
+//! use miette::Diagnostic;
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct AppConfig {
+//!     port: u16,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_diagnostic.toml");
+//! std::fs::write(&path, "port = [80")?;
+//!
+//! let Err(err) = ConfigLoader::default().with_config_file(&path).load::<AppConfig>() else {
+//!     panic!("expected a parse error");
+//! };
+//!
+//! assert_eq!(err.code().map(|code| code.to_string()).as_deref(), Some("konfik::file"));
+//! assert_eq!(err.labels().map(Iterator::count), Some(1));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::Error;
+use miette::{Diagnostic, LabeledSpan, SourceCode, SourceSpan};
+use std::{fmt::Display, iter};
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self {
+            Self::Io(_) => "konfik::io",
+            Self::Serde(_) => "konfik::json",
+            Self::Toml(_) | Self::TomlSerialize(_) => "konfik::toml",
+            Self::Yaml(_) => "konfik::yaml",
+            Self::ParseFileFormat(_) => "konfik::file_format",
+            Self::File { .. } => "konfik::file",
+            Self::PermissionDenied { .. } => "konfik::permission_denied",
+            Self::InvalidFile { .. } => "konfik::invalid_file",
+            Self::ConfigParse { .. } => "konfik::config_parse",
+            Self::Environment(_) => "konfik::environment",
+            Self::Validation(_) => "konfik::validation",
+            Self::MissingRequired(_) => "konfik::missing_required",
+            Self::Multiple(_) => "konfik::multiple",
+            Self::Remote(_) => "konfik::remote",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            Self::File { path, .. } => Some(Box::new(format!(
+                "fix the syntax error in {}",
+                path.display()
+            ))),
+            Self::PermissionDenied { .. } => Some(Box::new(
+                "the file exists, but the process isn't allowed to read it",
+            )),
+            Self::ParseFileFormat(_) => Some(Box::new(
+                "config files need a .json, .yaml or .toml extension",
+            )),
+            Self::MissingRequired(_) => Some(Box::new(
+                "set them in a config file, as env vars or as CLI args",
+            )),
+            Self::ConfigParse { .. } => Some(Box::new(
+                "check that every required field is set and has the right type",
+            )),
+            _ => None,
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self {
+            Self::File { snippet, .. } => Some(snippet),
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let Self::File {
+            span: Some(span),
+            source,
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        // TOML messages already quote the offending line, the label only marks it
+        let message = match source.as_ref() {
+            Self::Toml(_) => None,
+            Self::Serde(e) => Some(e.to_string()),
+            _ => return None,
+        };
+
+        Some(Box::new(iter::once(LabeledSpan::new_with_span(
+            message,
+            SourceSpan::from(span.clone()),
+        ))))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        match self {
+            Self::Multiple(errors) => Some(Box::new(
+                errors.iter().map(|error| error as &dyn Diagnostic),
+            )),
+            _ => None,
+        }
+    }
+}
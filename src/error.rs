@@ -2,9 +2,11 @@
 // Copyright (c) 2025 kingananas20
 
 use crate::config_loader::ParseFileFormatError;
+use std::{ops::Range, path::PathBuf};
 
 /// Error type used in the crate
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Io error
     #[error("IO error: {0}")]
@@ -18,14 +20,57 @@ pub enum Error {
     #[error("TOML error: {0}")]
     Toml(#[from] toml::de::Error),
 
-    /// Yaml error
+    /// Toml serialization error
+    #[error("TOML serialization error: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    /// Yaml error, wraps the error of whichever YAML backend is enabled
+    ///
+    /// `From` is implemented for the errors of every enabled backend.
     #[error("YAML error: {0}")]
-    Yaml(#[from] serde_yaml::Error),
+    Yaml(Box<dyn std::error::Error + Send + Sync>),
 
     /// Parse file format error
     #[error("Parse file format error")]
     ParseFileFormat(#[from] ParseFileFormatError),
 
+    /// A config file that exists but couldn't be parsed
+    #[error("failed to parse {}: {source}", path.display())]
+    File {
+        /// Path or URL of the file
+        path: PathBuf,
+        /// The line of the file the parse error points at, empty if the error has no location
+        ///
+        /// Only this line is kept, so the rest of the file, and any secret in it, doesn't end
+        /// up in logs.
+        snippet: String,
+        /// Byte range of the parse error in `snippet`
+        span: Option<Range<usize>>,
+        /// The parse error
+        #[source]
+        source: Box<Self>,
+    },
+
+    /// A config file that exists, but the process isn't allowed to read
+    #[error("permission denied reading {}, check the owner and mode of the file", path.display())]
+    PermissionDenied {
+        /// Path of the file
+        path: PathBuf,
+        /// The error of reading the file
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A config file rejected by a per file validator
+    #[error("invalid config file {}: {source}", path.display())]
+    InvalidFile {
+        /// Path or URL of the file
+        path: PathBuf,
+        /// The error of the validator
+        #[source]
+        source: Box<Self>,
+    },
+
     /// Error if parsing fails because of missing fields
     #[error("Config parsing error for type {type_name}: {source:?}")]
     ConfigParse {
@@ -43,4 +88,39 @@ pub enum Error {
     /// Validation error
     #[error("Validation error: {0}")]
     Validation(String),
+
+    /// Required fields that no source provides, found by [`ConfigLoader::preflight`](crate::ConfigLoader::preflight)
+    #[error("missing required config values: {}", .0.join(", "))]
+    MissingRequired(Vec<String>),
+
+    /// Multiple errors, e.g. the failures of validators in [`ValidationMode::Collect`](crate::ValidationMode::Collect)
+    #[error("{} errors: {}", .0.len(), join_errors(.0))]
+    Multiple(Vec<Self>),
+
+    /// Error while fetching config from a remote location
+    #[error("Remote error: {0}")]
+    Remote(String),
+}
+
+#[cfg(feature = "yaml-serde_yaml")]
+impl From<serde_yaml::Error> for Error {
+    fn from(e: serde_yaml::Error) -> Self {
+        Self::Yaml(Box::new(e))
+    }
+}
+
+#[cfg(feature = "yaml-serde_yml")]
+impl From<serde_yml::Error> for Error {
+    fn from(e: serde_yml::Error) -> Self {
+        Self::Yaml(Box::new(e))
+    }
+}
+
+/// Joins the messages of `errors` for [`Error::Multiple`].
+fn join_errors(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
@@ -27,7 +27,7 @@
 //!
 //! ### Basic Usage
 //!
-//! ```rust
+//! ```rust,no_run
 //! use konfik::{ConfigLoader, LoadConfig, Konfik};
 //! use serde::Deserialize;
 //!
@@ -49,7 +49,7 @@
 //!
 //! ### Advanced Configuration
 //!
-//! ```rust
+//! ```rust,no_run
 //! use konfik::{ConfigLoader, Error, Konfik};
 //! use serde::Deserialize;
 //! use clap::Parser;
@@ -67,7 +67,6 @@
 //!     let config = ConfigLoader::default()
 //!         .with_env_prefix("MYAPP")           // Environment variables: MYAPP_DATABASE_URL, etc.
 //!         .with_config_file("app.toml")       // Additional config file
-//!         .with_cli()                         // Enable CLI argument parsing
 //!         .with_validation(|config| {         // Custom validation
 //!             if let Some(port) = config.get("port").and_then(|v| v.as_u64()) {
 //!                 if port > 65535 {
@@ -76,7 +75,7 @@
 //!             }
 //!             Ok(())
 //!         })
-//!         .load::<AppConfig>()?;
+//!         .load_with_cli::<AppConfig>()?; // CLI args override env vars and files
 //!
 //!     println!("Loaded config: {:#?}", config);
 //!     Ok(())
@@ -91,6 +90,77 @@
 //! 2. **Environment Variables**
 //! 3. **Configuration Files** (lowest priority)
 //!
+//! ```rust
+//! use konfik::{ConfigLoader, Konfik, Nested};
+//!
+//! #[derive(serde::Deserialize, Konfik, clap::Parser)]
+//! struct Config {
+//!     #[arg(long)]
+//!     port: u16,
+//!     #[konfik(nested)]
+//!     #[command(flatten)]
+//!     db: Database,
+//! }
+//!
+//! #[derive(serde::Deserialize, Nested, clap::Args)]
+//! struct Database {
+//!     #[arg(long)]
+//!     host: String,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_precedence.toml");
+//! std::fs::write(&path, "port = 1\n[db]\nhost = \"file\"")?;
+//!
+//! let env = [("PRECEDENCE_PORT", "2"), ("PRECEDENCE_DB_HOST", "env")];
+//! let load = |env: &[(&str, &str)], args: &[&str]| {
+//!     ConfigLoader::default()
+//!         .with_config_file(&path)
+//!         .with_env_prefix("PRECEDENCE")
+//!         .with_env_vars(env.iter().copied())
+//!         .with_cli_args(args)
+//!         .load_with_cli::<Config>()
+//!         .map(|config| (config.port, config.db.host))
+//! };
+//!
+//! // CLI wins over env and files
+//! assert_eq!(load(&env, &["myapp", "--port", "3", "--host", "cli"])?, (3, "cli".into()));
+//! // env wins over files
+//! assert_eq!(load(&env, &["myapp"])?, (2, "env".into()));
+//! // files alone
+//! assert_eq!(load(&[], &["myapp"])?, (1, "file".into()));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Fields marked `#[konfik(precedence = "file")]` (or `"env"`, `"credential"`, `"cli"`, `"remote"`, `"custom"`)
+//! always take the value of that source if it sets them, e.g. for operator locked settings:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[konfik(precedence = "file")]
+//!     max_connections: u32,
+//!     port: u16,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_field_precedence.toml");
+//! std::fs::write(&path, "max_connections = 10\nport = 80")?;
+//! // SAFETY: nothing else touches the environment in this example
+//! unsafe {
+//!     std::env::set_var("LOCKED_MAX_CONNECTIONS", "1000");
+//!     std::env::set_var("LOCKED_PORT", "8080");
+//! }
+//!
+//! let config = ConfigLoader::default()
+//!     .with_config_file(&path)
+//!     .with_env_prefix("LOCKED")
+//!     .load::<Config>()?;
+//!
+//! assert_eq!(config.max_connections, 10);
+//! assert_eq!(config.port, 8080);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
 //! ### Configuration Files
 //!
 //! By default, konfik looks for these files in the current directory:
@@ -101,11 +171,66 @@
 //!
 //! You can specify custom files:
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use konfik::{ConfigLoader, Konfik};
+//! # #[derive(serde::Deserialize, Konfik)]
+//! # struct AppConfig { port: u16 }
+//! # fn main() -> Result<(), konfik::Error> {
 //! let config = ConfigLoader::default()
 //!     .with_config_file("custom.toml")
-//!     .with_config_files(&["/etc/myapp/config.yaml", "config.json"])
+//!     .with_config_files(vec!["/etc/myapp/config.yaml", "config.json"])
+//!     .load::<AppConfig>()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Formats can be mixed, every file is parsed according to its extension. Files are merged
+//! in the order they were added, later files override earlier ones. For an explicit order
+//! independent of the call order, use [`ConfigLoader::with_config_layers`] with a [`Priority`],
+//! which orders custom sources too, or [`ConfigLoader::with_config_file_prio`] with a number:
+//!
+//! ```rust,no_run
+//! # use konfik::{ConfigLoader, Konfik, Priority};
+//! # #[derive(serde::Deserialize, Konfik)]
+//! # struct AppConfig { port: u16 }
+//! # fn main() -> Result<(), konfik::Error> {
+//! let config = ConfigLoader::default()
+//!     .with_config_layers(vec![("generated.json", Priority::High), ("base.yaml", Priority::Low)])
 //!     .load::<AppConfig>()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### YAML Backends
+//!
+//! YAML files are parsed with `serde_yaml` by default. Since `serde_yaml` is no longer
+//! maintained, the `yaml-serde_yml` feature switches the backend to `serde_yml`:
+//!
+//! ```toml
+//! [dependencies]
+//! konfik = { version = "0.2", default-features = false, features = ["yaml-serde_yml"] }
+//! ```
+//!
+//! Both backends produce the same result:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct AppConfig {
+//!     name: String,
+//!     ports: Vec<u16>,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_yaml_backend.yaml");
+//! std::fs::write(&path, "name: konfik\nports: [80, 443]\n")?;
+//!
+//! let config = ConfigLoader::default()
+//!     .with_config_file(&path)
+//!     .load::<AppConfig>()?;
+//!
+//! assert_eq!(config.name, "konfik");
+//! assert_eq!(config.ports, [80, 443]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 //!
 //! ### Environment Variables
@@ -113,6 +238,8 @@
 //! Environment variables are automatically mapped from your struct fields:
 //!
 //! ```rust
+//! # use konfik::Konfik;
+//! # use serde::Deserialize;
 //! #[derive(Deserialize, Konfik)]
 //! struct Config {
 //!     database_url: String,  // DATABASE_URL
@@ -123,10 +250,71 @@
 //!
 //! With a prefix:
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use konfik::{ConfigLoader, Konfik};
+//! # #[derive(serde::Deserialize, Konfik)]
+//! # struct Config { port: u16 }
+//! # fn main() -> Result<(), konfik::Error> {
 //! let config = ConfigLoader::default()
 //!     .with_env_prefix("MYAPP")  // MYAPP_DATABASE_URL, MYAPP_API_KEY, etc.
 //!     .load::<Config>()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Fields marked `#[konfik(env = "NAME")]` read these env vars instead, with the prefix.
+//! Names starting with `=` are absolute and taken as written, e.g. `env = "=DATABASE_URL"`
+//! for a variable another tool already sets. With several names, the first one that is
+//! set wins:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     // MYAPP_DATABASE_URL, MYAPP_PG_URL, then DB_DSN
+//!     #[konfik(env = "DATABASE_URL", env = "PG_URL", env = "=DB_DSN")]
+//!     database_url: String,
+//! }
+//!
+//! let load = |vars: &[(&str, &str)]| {
+//!     ConfigLoader::default()
+//!         .with_env_prefix("MYAPP")
+//!         .with_env_vars(vars.iter().copied())
+//!         .load::<Config>()
+//! };
+//!
+//! let config = load(&[("DB_DSN", "postgres://dsn"), ("MYAPP_PG_URL", "postgres://pg")])?;
+//! assert_eq!(config.database_url, "postgres://pg");
+//!
+//! let config = load(&[("DB_DSN", "postgres://dsn"), ("PG_URL", "postgres://pg")])?;
+//! assert_eq!(config.database_url, "postgres://dsn");
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! A variable that is set but isn't valid UTF-8 fails the load with an
+//! [`Error::Environment`] naming it, instead of being treated as unset:
+//!
+//! ```rust
+//! # #[cfg(unix)]
+//! # fn main() {
+//! # use konfik::{ConfigLoader, Error, Konfik};
+//! use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[serde(default)]
+//!     name: String,
+//! }
+//!
+//! // SAFETY: nothing else touches the environment in this example
+//! unsafe { std::env::set_var("NON_UTF8_NAME", OsStr::from_bytes(b"caf\xe9")) };
+//!
+//! let result = ConfigLoader::default().with_env_prefix("NON_UTF8").load::<Config>();
+//! let Err(Error::Environment(message)) = result else { panic!("expected an env error") };
+//! assert!(message.contains("NON_UTF8_NAME"));
+//! # }
+//! # #[cfg(not(unix))]
+//! # fn main() {}
 //! ```
 //!
 //! ### CLI Arguments
@@ -135,23 +323,486 @@
 //! missing and makes those required in the CLI:
 //!
 //! ```rust
+//! # use konfik::Konfik;
+//! # use serde::Deserialize;
 //! #[derive(Deserialize, Konfik)]
-//! struct Konfik {
+//! struct Config {
 //!     database_url: String,  // --database-url
 //!     max_connections: u32,  // --max-connections
 //!     debug: bool,          // --debug (flag, no value needed)
 //! }
 //! ```
 //!
+//! Fields marked `#[konfik(cli_skip)]` are still loaded from files and env vars but left
+//! out of the CLI: they are hidden from `--help` and values passed on the command line
+//! are ignored.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik, clap::Parser)]
+//! struct Config {
+//!     #[arg(long)]
+//!     port: u16,
+//!     #[konfik(cli_skip)]
+//!     #[arg(long)]
+//!     api_token: String,
+//! }
+//!
+//! // SAFETY: nothing else touches the environment in this example
+//! unsafe { std::env::set_var("CLI_SKIP_API_TOKEN", "from-env") };
+//!
+//! let config = ConfigLoader::default()
+//!     .with_env_prefix("CLI_SKIP")
+//!     .with_cli_args(["myapp", "--port", "80", "--api-token", "from-cli"])
+//!     .load_with_cli::<Config>()?;
+//!
+//! assert_eq!(config.api_token, "from-env");
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! `#[konfik(cli = "NAME")]` renames the long flag of a field. Together with
+//! `#[konfik(env = "NAME")]` a field can keep the names an existing deployment uses:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik, clap::Parser)]
+//! struct Config {
+//!     #[konfik(env = "=PG_DSN", cli = "pg-dsn")]
+//!     #[arg(long)]
+//!     database_url: String,
+//!     #[konfik(cli = "pool")]
+//!     #[arg(long)]
+//!     max_connections: Option<u32>,
+//! }
+//!
+//! // SAFETY: nothing else touches the environment in this example
+//! unsafe { std::env::set_var("PG_DSN", "postgres://from-env") };
+//!
+//! let config = ConfigLoader::default()
+//!     .with_env_prefix("RENAMED")
+//!     .with_cli_args(["myapp", "--pool", "8"])
+//!     .load_with_cli::<Config>()?;
+//! assert_eq!(config.database_url, "postgres://from-env");
+//! assert_eq!(config.max_connections, Some(8));
+//!
+//! let config = ConfigLoader::default()
+//!     .with_cli_args(["myapp", "--pg-dsn", "postgres://from-cli"])
+//!     .load_with_cli::<Config>()?;
+//! assert_eq!(config.database_url, "postgres://from-cli");
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! clap arg groups take part in the merge. A member of an exclusive group passed on the
+//! command line replaces the other members from lower priority sources. Required groups
+//! can also be satisfied by files and env vars; if no source sets any member, loading
+//! fails with an [`Error::Validation`] instead of exiting.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik, clap::Parser)]
+//! #[command(group(clap::ArgGroup::new("auth").args(["token", "password"]).required(true)))]
+//! struct Config {
+//!     #[arg(long)]
+//!     token: Option<String>,
+//!     #[arg(long)]
+//!     password: Option<String>,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_arg_groups.toml");
+//! std::fs::write(&path, "password = \"hunter2\"")?;
+//!
+//! let config = ConfigLoader::default()
+//!     .with_config_file(&path)
+//!     .with_cli_args(["myapp", "--token", "abc"])
+//!     .load_with_cli::<Config>()?;
+//!
+//! assert_eq!(config.token.as_deref(), Some("abc"));
+//! assert_eq!(config.password, None);
+//!
+//! let result = ConfigLoader::default()
+//!     .with_cli_args(["myapp"])
+//!     .load_with_cli::<Config>();
+//!
+//! assert!(matches!(result, Err(konfik::Error::Validation(_))));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
 //! ## Supported Types
 //!
-//! `Konfik` supports all types.
+//! `Konfik` supports all types that own their data. The configuration is merged into a
+//! temporary `serde_json::Value` before it is deserialized, so configs can't borrow from
+//! it. Use `String` instead of `&str` and `Vec<T>` instead of `&[T]`; the derive rejects
+//! structs with lifetimes or reference fields:
+//!
+//! ```rust,compile_fail
+//! # use konfik::Konfik;
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config<'a> {
+//!     name: &'a str, // error: konfik configs must own their data
+//! }
+//! ```
+//!
+//! Generic configs are supported, nested type parameters need a `ConfigMeta` bound:
+//!
+//! ```rust
+//! use konfik::{ConfigLoader, Konfik, Nested, config_meta::ConfigMeta};
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Service<T: ConfigMeta> {
+//!     name: String,
+//!     #[konfik(nested)]
+//!     settings: T,
+//! }
+//!
+//! #[derive(serde::Deserialize, Nested)]
+//! struct Http {
+//!     port: u16,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_generic.toml");
+//! std::fs::write(&path, "name = \"api\"\n[settings]\nport = 8080")?;
+//!
+//! let config = ConfigLoader::default()
+//!     .with_config_file(&path)
+//!     .load::<Service<Http>>()?;
+//!
+//! assert_eq!(config.settings.port, 8080);
+//! assert!(Service::<Http>::config_metadata().iter().any(|f| f.path == "settings.port"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Enums, like serde tagged unions of different backends, can derive `Konfik` too. Which
+//! fields exist depends on the variant, so enums have no field metadata: they are loaded
+//! from files and custom sources, and serde checks the fields of the chosen variant. Env
+//! vars and CLI args aren't mapped to them.
+//!
+//! ```rust
+//! use konfik::{ConfigLoader, Konfik};
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! #[serde(tag = "backend", rename_all = "lowercase")]
+//! enum Storage {
+//!     Local { path: String },
+//!     S3 { bucket: String, region: String },
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_enum.toml");
+//! std::fs::write(&path, "backend = \"s3\"\nbucket = \"assets\"\nregion = \"eu-west-1\"")?;
+//!
+//! let config = ConfigLoader::default()
+//!     .with_config_file(&path)
+//!     .load::<Storage>()?;
+//!
+//! assert!(matches!(config, Storage::S3 { bucket, .. } if bucket == "assets"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ### Sizes
+//!
+//! Integer fields marked `#[konfik(size)]` accept human readable sizes from every source.
+//! SI units (`KB`, `MB`, `GB`, ...) are powers of 1000, binary units (`KiB`, `MiB`, `GiB`, ...)
+//! powers of 1024. Invalid sizes, and sizes that don't fit into a `u64`, are reported as
+//! [`Error::Validation`].
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[konfik(size)]
+//!     max_upload: u64,
+//!     #[konfik(size)]
+//!     buffer: u64,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_sizes.toml");
+//! std::fs::write(&path, "max_upload = \"10MB\"\nbuffer = \"4 KiB\"")?;
+//!
+//! let config = ConfigLoader::default().with_config_file(&path).load::<Config>()?;
+//!
+//! assert_eq!(config.max_upload, 10_000_000);
+//! assert_eq!(config.buffer, 4096);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ### Optional Defaults
+//!
+//! `Option` fields marked `#[konfik(default_if_none = "8080")]` get the value when no source
+//! sets them, as the lowest priority layer. The value is parsed for the type of the field:
+//! numbers and booleans are parsed like env values, strings are kept as they are. An explicit
+//! `null` still means `None`, unless the field is also marked `#[konfik(null_uses_default)]`.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[konfik(default_if_none = "8080")]
+//!     port: Option<u16>,
+//!     #[konfik(default_if_none = "localhost", null_uses_default)]
+//!     host: Option<String>,
+//!     #[konfik(default_if_none = "1234")]
+//!     pin: Option<String>,
+//! }
+//!
+//! let load = |content: &str| {
+//!     let path = std::env::temp_dir().join("konfik_default_if_none.json");
+//!     std::fs::write(&path, content)?;
+//!     ConfigLoader::default().with_config_file(&path).load::<Config>()
+//! };
+//!
+//! let absent = load("{}")?;
+//! let null = load(r#"{ "port": null, "host": null }"#)?;
+//! let set = load(r#"{ "port": 80, "host": "example.com" }"#)?;
+//!
+//! assert_eq!((absent.port, absent.host.as_deref()), (Some(8080), Some("localhost")));
+//! assert_eq!(absent.pin.as_deref(), Some("1234"));
+//! assert_eq!((null.port, null.host.as_deref()), (None, Some("localhost")));
+//! assert_eq!((set.port, set.host.as_deref()), (Some(80), Some("example.com")));
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! ### Explicit Nulls
+//!
+//! An absent key and a key set to `null` mean different things. Only JSON and YAML files
+//! and custom sources can set `null`; TOML has no null, and env vars and CLI args are
+//! absent when they aren't set. Through the pipeline:
+//!
+//! - **Merging**: `null` is a value. It overrides lower priority sources, while an absent
+//!   key keeps their value. To make a key absent again, use a
+//!   [delete sentinel](ConfigLoader::with_delete_sentinel).
+//! - **Required fields**: a required field set to `null` is missing, since `null` can't be
+//!   deserialized into it.
+//! - **Defaults**: `#[serde(default)]`, default functions and `default_if_none` only apply
+//!   to absent keys. `#[konfik(null_uses_default)]` makes `default_if_none` apply to `null`.
+//! - **Deserialization**: `null` and absent keys both become `None` in `Option` fields. With
+//!   [`explicit_null`], `Option<Option<T>>` fields keep them apart.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     // None: use the default proxy, Some(None): no proxy
+//!     #[serde(default, deserialize_with = "konfik::explicit_null")]
+//!     proxy: Option<Option<String>>,
+//! }
+//!
+//! let base = std::env::temp_dir().join("konfik_explicit_null_base.yaml");
+//! let local = std::env::temp_dir().join("konfik_explicit_null_local.json");
+//! let load = |base_content: &str, local_content: &str| {
+//!     std::fs::write(&base, base_content)?;
+//!     std::fs::write(&local, local_content)?;
+//!     ConfigLoader::default()
+//!         .with_config_file(&base)
+//!         .with_config_file(&local)
+//!         .load::<Config>()
+//! };
+//!
+//! assert_eq!(load("{}", "{}")?.proxy, None);
+//! assert_eq!(load("proxy: socks5://proxy", r#"{ "proxy": null }"#)?.proxy, Some(None));
+//! assert_eq!(
+//!     load("proxy: ~", r#"{ "proxy": "socks5://proxy" }"#)?.proxy,
+//!     Some(Some("socks5://proxy".into()))
+//! );
+//! assert_eq!(load("proxy: socks5://proxy", "{}")?.proxy, Some(Some("socks5://proxy".into())));
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! ### Default Functions
+//!
+//! A `#[serde(default = "path")]` function is only called by serde, so its value doesn't
+//! show up in the merged config. Fields also marked `#[konfik(eval_default)]` get the
+//! function's result filled in when no source sets them, so it is visible in
+//! [`ConfigLoader::load_merged`], dumps and provenance just like a value from a file. The
+//! function's return type has to implement `Serialize` then.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! # use serde_json::json;
+//! fn default_port() -> u16 {
+//!     8080
+//! }
+//!
+//! // Not `Serialize`, so its default is left to serde
+//! #[derive(serde::Deserialize, Debug, PartialEq)]
+//! struct Retries(u8);
+//!
+//! fn default_retries() -> Retries {
+//!     Retries(3)
+//! }
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[serde(default = "default_port")]
+//!     #[konfik(eval_default)]
+//!     port: u16,
+//!     #[serde(default = "default_retries")]
+//!     retries: Retries,
+//! }
+//!
+//! let loader = ConfigLoader::default();
+//! assert_eq!(loader.load_merged::<Config>()?, json!({ "port": 8080 }));
+//! let config = loader.load::<Config>()?;
+//! assert_eq!((config.port, config.retries), (8080, Retries(3)));
+//! # Ok::<(), konfik::Error>(())
+//! ```
+//!
+//! ### Field Transforms
+//!
+//! Fields marked `#[konfik(transform = "path::to::function")]` pass their merged value through
+//! the function before validation. It is a [`config_meta::Transform`], a
+//! `fn(serde_json::Value) -> Result<serde_json::Value, Error>`, and only runs when some
+//! source sets the field.
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Error, Konfik};
+//! use serde_json::Value;
+//!
+//! fn normalize_region(value: Value) -> Result<Value, Error> {
+//!     match value {
+//!         Value::String(region) => Ok(Value::String(region.trim().to_lowercase())),
+//!         other => Err(Error::Validation(format!("region must be a string, got {other}"))),
+//!     }
+//! }
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[konfik(transform = "normalize_region")]
+//!     region: String,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_transform.toml");
+//! std::fs::write(&path, "region = \"  EU-West-1 \"")?;
+//!
+//! let config = ConfigLoader::default().with_config_file(&path).load::<Config>()?;
+//! assert_eq!(config.region, "eu-west-1");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ### Renamed Keys
+//!
+//! Fields marked `#[konfik(deprecated_alias = "old_name")]` still accept the old name in
+//! files and env vars. The value is moved to the current name and the handler set with
+//! [`ConfigLoader::with_warning_handler`] is told about it. If both names are set, the
+//! current name wins.
+//!
+//! ### Dates and Times
+//!
+//! TOML dates and times are read as RFC 3339 strings, so they deserialize into types like
+//! `chrono::NaiveDate`, `chrono::NaiveTime`, `chrono::NaiveDateTime` and `chrono::DateTime`:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     release: NaiveDate,
+//!     backup_at: NaiveTime,
+//!     maintenance: NaiveDateTime,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_dates.toml");
+//! std::fs::write(
+//!     &path,
+//!     "release = 2025-01-01\nbackup_at = 13:00:00\nmaintenance = 2025-01-01T02:30:00",
+//! )?;
+//!
+//! let config = ConfigLoader::default().with_config_file(&path).load::<Config>()?;
+//!
+//! assert_eq!(config.release, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+//! assert_eq!(config.backup_at, NaiveTime::from_hms_opt(13, 0, 0).unwrap());
+//! assert_eq!(config.maintenance, config.release.and_hms_opt(2, 30, 0).unwrap());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ### Numbers
+//!
+//! Numbers are converted to the kind of their field before deserializing. Integers are
+//! widened for float fields, and floats without a fraction, e.g. `8080.0` from a JSON
+//! generator, are accepted by integer fields:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     ratio: f64,
+//!     port: u16,
+//!     weights: Vec<f32>,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_numbers.json");
+//! std::fs::write(&path, r#"{ "ratio": 1, "port": 8080.0, "weights": [1, 2.5] }"#)?;
+//!
+//! let config = ConfigLoader::default().with_config_file(&path).load::<Config>()?;
+//!
+//! assert!((config.ratio - 1.0).abs() < f64::EPSILON);
+//! assert_eq!(config.port, 8080);
+//! assert_eq!(config.weights, [1.0, 2.5]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ### Big Numbers
+//!
+//! Numbers are stored as `i64`, `u64` or `f64` while merging. The `arbitrary_precision`
+//! feature enables the feature of the same name in `serde_json`, so larger numbers from
+//! JSON files and env vars keep all their digits, e.g. in a `serde_json::Number` field.
+//! TOML and YAML parse numbers themselves, so they are still limited to 64 bits.
+//!
+//! For full control over the final deserialization, get the merged config with
+//! [`ConfigLoader::load_merged`] and deserialize it yourself:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[serde(default)]
+//!     port: u16,
+//! }
+//!
+//! let merged = ConfigLoader::default().load_merged::<Config>()?;
+//! let config = serde_json::from_value::<Config>(merged)?;
+//! # assert_eq!(config.port, 0);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! ## Error Reporting
+//!
+//! Config files that exist but can't be parsed fail the load with an [`Error::File`] naming
+//! the file, instead of being skipped like missing files:
+//!
+//! ```rust
+//! # use konfik::{ConfigLoader, Error, Konfik};
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct Config {
+//!     #[serde(default)]
+//!     port: u16,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_malformed.toml");
+//! std::fs::write(&path, "[server\nport = 8080")?;
+//!
+//! let result = ConfigLoader::default().with_config_file(&path).load::<Config>();
+//! let Err(Error::File { path: failed, .. }) = result else { panic!("expected a file error") };
+//! assert_eq!(failed, path);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! With the `miette` feature, [`Error`] implements `miette::Diagnostic`, so CLI
+//! apps can render TOML and JSON syntax errors with the offending line and a caret:
+//!
+//! ```toml
+//! [dependencies]
+//! konfik = { version = "0.2", features = ["miette"] }
+//! ```
 //!
 //! ## Validation
 //!
 //! Add custom validation logic:
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use konfik::{ConfigLoader, Error, Konfik};
+//! # #[derive(serde::Deserialize, Konfik)]
+//! # struct AppConfig { port: u16 }
+//! # fn main() -> Result<(), Error> {
 //! let config = ConfigLoader::default()
 //!     .with_validation(|config| {
 //!         // Validate port range
@@ -172,20 +823,90 @@
 //!         Ok(())
 //!     })
 //!     .load::<AppConfig>()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### Patterns
+//!
+//! With the `regex` feature, string fields can be matched against a pattern with
+//! `#[konfik(regex = "...")]`. Invalid patterns fail to compile, values that don't match
+//! fail the load with an [`Error::Validation`] naming the field and the pattern. For `Vec`
+//! fields every item has to match.
+//!
+//! ```rust
+//! # #[cfg(feature = "regex")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use konfik::{ConfigLoader, Error, Konfik};
+//!
+//! #[derive(serde::Deserialize, Konfik)]
+//! struct AppConfig {
+//!     #[konfik(regex = r"^v\d+\.\d+\.\d+$")]
+//!     version: String,
+//! }
+//!
+//! let path = std::env::temp_dir().join("konfik_regex.toml");
+//! let loader = ConfigLoader::default().with_config_file(&path);
+//!
+//! std::fs::write(&path, "version = \"v1.2.3\"")?;
+//! assert_eq!(loader.load::<AppConfig>()?.version, "v1.2.3");
+//!
+//! std::fs::write(&path, "version = \"1.2\"")?;
+//! let Err(Error::Validation(message)) = loader.load::<AppConfig>() else {
+//!     panic!("expected a validation error");
+//! };
+//! assert!(message.contains("version"));
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "regex"))]
+//! # fn main() {}
+//! ```
+//!
+// Without the feature the attribute itself is rejected, so the example would fail for the
+// wrong reason
+#![cfg_attr(feature = "regex", doc = "```rust,compile_fail")]
+#![cfg_attr(not(feature = "regex"), doc = "```rust,ignore")]
+//! #[derive(serde::Deserialize, konfik::Konfik)]
+//! struct AppConfig {
+//!     #[konfik(regex = "v(\\d+")]
+//!     version: String,
+//! }
 //! ```
 
 mod config_loader;
 pub mod config_meta;
+mod config_report;
+pub mod config_source;
+mod default_loader;
+mod deserialize;
+#[cfg(feature = "miette")]
+mod diagnostic;
 mod error;
+mod loaded_config;
+pub mod merge;
+mod priority;
+mod source;
+mod validation;
 
-pub use config_loader::ConfigLoader;
+pub use config_loader::{ConfigLoader, FileFormat, Subscription};
+pub use config_report::{ConfigReport, FieldReport};
+pub use default_loader::{default_loader, set_default_loader};
+pub use deserialize::{deserialize_at, explicit_null};
 pub use error::Error;
 pub use konfik_derive::{Konfik, Nested};
+pub use loaded_config::LoadedConfig;
+pub use merge::{ArrayMerge, ConfigChange};
+pub use priority::Priority;
+pub use source::Source;
+pub use validation::{ImmutableMode, ValidationMode};
 
 /// Simple trait for loading configuration
 pub trait LoadConfig: Sized {
     /// Load configuration from all available sources
     ///
+    /// The derived implementation uses the loader set with [`set_default_loader`], or
+    /// [`ConfigLoader::default`] if none was set.
+    ///
     /// # Errors
     ///
     /// Both functions return an `Error` if any of the following occur:
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+use crate::config_meta::ConfigMeta;
+use serde_json::Value;
+use std::ops::Deref;
+
+/// A loaded configuration together with the merged value it was deserialized from
+///
+/// Returned by [`ConfigLoader::load_with_raw`](crate::ConfigLoader::load_with_raw). It
+/// dereferences to `T`, and [`get_raw`](Self::get_raw) reads keys that `T` doesn't know.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig<T> {
+    config: T,
+    raw: Value,
+}
+
+impl<T> LoadedConfig<T> {
+    pub(crate) const fn new(config: T, raw: Value) -> Self {
+        Self { config, raw }
+    }
+
+    /// The deserialized configuration
+    #[must_use]
+    pub const fn config(&self) -> &T {
+        &self.config
+    }
+
+    /// The merged, normalized and validated configuration
+    #[must_use]
+    pub const fn raw(&self) -> &Value {
+        &self.raw
+    }
+
+    /// Consumes the wrapper and returns the deserialized configuration
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.config
+    }
+
+    /// Consumes the wrapper and returns the configuration and the merged value
+    #[must_use]
+    pub fn into_parts(self) -> (T, Value) {
+        (self.config, self.raw)
+    }
+}
+
+impl<T: ConfigMeta> LoadedConfig<T> {
+    /// Gets the merged value at the dotted `path`, including keys without a field in `T`
+    #[must_use]
+    pub fn get_raw(&self, path: &str) -> Option<&Value> {
+        T::get_nested_value(&self.raw, path)
+    }
+}
+
+impl<T> Deref for LoadedConfig<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.config
+    }
+}
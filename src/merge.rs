@@ -0,0 +1,253 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Merge and coercion engine without I/O
+//!
+//! Everything in this module only works on [`serde_json::Value`]s and doesn't touch the
+//! filesystem, the environment or the CLI. It can be used to merge configs from custom
+//! sources, e.g. in WASM or other constrained environments.
+//!
+//! The crate itself still needs `std`. The loaders and their dependencies (clap, the file
+//! format parsers) are always built, there is no `std` feature that leaves them out.
+//!
+//! ```rust
+//! use konfik::merge::{merge, parse_value};
+//! use serde_json::json;
+//!
+//! let defaults = json!({ "server": { "host": "localhost", "port": 80 } });
+//! let overrides = json!({ "server": { "port": parse_value("8080") } });
+//!
+//! assert_eq!(
+//!     merge(defaults, overrides),
+//!     json!({ "server": { "host": "localhost", "port": 8080 } })
+//! );
+//! ```
+
+use serde_json::{Map, Value};
+
+/// How arrays at the same key are combined, see [`ConfigLoader::with_array_merge`](crate::ConfigLoader::with_array_merge)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ArrayMerge {
+    /// The array of the higher priority source replaces the other one
+    #[default]
+    Replace,
+    /// The array of the higher priority source is appended to the other one
+    Append,
+}
+
+/// Deep merges `overlay` into `base`
+///
+/// Objects are merged key by key, every other value in `overlay` replaces the one in `base`.
+#[must_use]
+pub fn merge(base: Value, overlay: Value) -> Value {
+    merge_with(base, overlay, ArrayMerge::Replace, None, &mut |_, _| {})
+}
+
+/// A change made by [`merge_with`] at a dotted path
+pub(crate) enum Change<'a> {
+    /// The key was set to `new`, replacing `old` if it had a value
+    Set {
+        old: Option<&'a Value>,
+        new: &'a Value,
+    },
+    /// The overlay array was appended to the one at the key
+    Appended(&'a Value),
+    /// The key was removed by the delete sentinel
+    Removed,
+}
+
+/// Deep merges `overlay` into `base` like [`merge`], reporting every change to `on_change`
+///
+/// Arrays are combined according to `arrays`, keys the overlay sets to `delete` are removed.
+pub(crate) fn merge_with(
+    base: Value,
+    overlay: Value,
+    arrays: ArrayMerge,
+    delete: Option<&Value>,
+    on_change: &mut dyn FnMut(&str, Change<'_>),
+) -> Value {
+    merge_at(base, overlay, "", arrays, delete, on_change)
+}
+
+fn merge_at(
+    base: Value,
+    overlay: Value,
+    path: &str,
+    arrays: ArrayMerge,
+    delete: Option<&Value>,
+    on_change: &mut dyn FnMut(&str, Change<'_>),
+) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base_map), Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+
+                if delete == Some(&value) {
+                    base_map.remove(&key);
+                    on_change(&key_path, Change::Removed);
+                    continue;
+                }
+
+                let merged = match base_map.remove(&key) {
+                    Some(base_value)
+                        if (base_value.is_object() && value.is_object())
+                            || (arrays == ArrayMerge::Append
+                                && base_value.is_array()
+                                && value.is_array()) =>
+                    {
+                        merge_at(base_value, value, &key_path, arrays, delete, on_change)
+                    }
+                    old => {
+                        on_change(
+                            &key_path,
+                            Change::Set {
+                                old: old.as_ref(),
+                                new: &value,
+                            },
+                        );
+                        value
+                    }
+                };
+                base_map.insert(key, merged);
+            }
+            Value::Object(base_map)
+        }
+        (Value::Array(mut base_items), Value::Array(overlay_items))
+            if arrays == ArrayMerge::Append =>
+        {
+            base_items.extend(overlay_items);
+            let merged = Value::Array(base_items);
+            on_change(path, Change::Appended(&merged));
+            merged
+        }
+        (_, overlay) => {
+            on_change(
+                path,
+                Change::Set {
+                    old: None,
+                    new: &overlay,
+                },
+            );
+            overlay
+        }
+    }
+}
+
+/// Wraps `value` in objects so it sits at the dotted `path`
+///
+/// ```rust
+/// use konfik::merge::at_path;
+/// use serde_json::json;
+///
+/// assert_eq!(at_path("logging.level", json!("debug")), json!({ "logging": { "level": "debug" } }));
+/// ```
+#[must_use]
+pub fn at_path(path: &str, value: Value) -> Value {
+    path.rsplit('.').fold(value, |value, key| {
+        Value::Object(Map::from_iter([(key.to_string(), value)]))
+    })
+}
+
+/// Coerces a raw string value, e.g. from an env var, into the most specific JSON value
+///
+/// Booleans and numbers are parsed, JSON arrays and objects are decoded and everything
+/// else is kept as a string.
+#[must_use]
+pub fn parse_value(value: &str) -> Value {
+    // Try parsing as different types
+    if let Ok(b) = value.parse::<bool>() {
+        return Value::Bool(b);
+    }
+
+    // Keep the exact digits, they are only parsed when deserializing
+    #[cfg(feature = "arbitrary_precision")]
+    if value
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b))
+        && let Ok(n) = serde_json::from_str::<serde_json::Number>(value)
+    {
+        return Value::Number(n);
+    }
+
+    if let Ok(n) = value.parse::<i64>() {
+        return Value::Number(n.into());
+    }
+
+    if let Ok(n) = value.parse::<f64>()
+        && let Some(num) = serde_json::Number::from_f64(n)
+    {
+        return Value::Number(num);
+    }
+
+    // Try parsing as JSON array/object
+    if ((value.starts_with('[') && value.ends_with(']'))
+        || (value.starts_with('{') && value.ends_with('}')))
+        && let Ok(json) = serde_json::from_str(value)
+    {
+        return json;
+    }
+
+    Value::String(value.to_string())
+}
+
+/// A value that differs between two configs, found by [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Dotted path of the value
+    pub path: String,
+    /// The value in the old config, `None` if it was absent
+    pub old: Option<Value>,
+    /// The value in the new config, `None` if it was removed
+    pub new: Option<Value>,
+}
+
+/// Lists the values that differ between `old` and `new`, ordered by path
+///
+/// Objects are compared key by key, every other value as a whole.
+///
+/// ```rust
+/// use konfik::merge::{ConfigChange, diff};
+/// use serde_json::json;
+///
+/// let old = json!({ "server": { "host": "localhost", "port": 80 } });
+/// let new = json!({ "server": { "host": "localhost", "port": 8080 } });
+///
+/// assert_eq!(diff(&old, &new), [ConfigChange {
+///     path: "server.port".into(),
+///     old: Some(json!(80)),
+///     new: Some(json!(8080)),
+/// }]);
+/// ```
+#[must_use]
+pub fn diff(old: &Value, new: &Value) -> Vec<ConfigChange> {
+    fn walk(old: Option<&Value>, new: Option<&Value>, path: &str, changes: &mut Vec<ConfigChange>) {
+        if let (Some(Value::Object(old)), Some(Value::Object(new))) = (old, new) {
+            let mut keys = old.keys().chain(new.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let key_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                walk(old.get(key), new.get(key), &key_path, changes);
+            }
+        } else if old != new {
+            changes.push(ConfigChange {
+                path: path.to_string(),
+                old: old.cloned(),
+                new: new.cloned(),
+            });
+        }
+    }
+
+    let mut changes = Vec::new();
+    walk(Some(old), Some(new), "", &mut changes);
+    changes
+}
@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Priorities of explicitly layered config files and custom sources.
+
+/// Where a config file or a custom source is merged relative to the other files and sources
+///
+/// Files ([`ConfigLoader::with_config_layers`](crate::ConfigLoader::with_config_layers)) and
+/// custom sources ([`ConfigLoader::with_source_priority`](crate::ConfigLoader::with_source_priority))
+/// are ordered the same way: all [`Low`](Self::Low) files and sources are merged first, then
+/// the [`Normal`](Self::Normal) ones, then the [`High`](Self::High) ones. Within a priority,
+/// files are merged before sources. Files are sorted by their numeric priority
+/// ([`ConfigLoader::with_config_file_prio`](crate::ConfigLoader::with_config_file_prio), `0`
+/// for every other file), files and sources with the same priority are merged in the order
+/// they were added. Env vars and CLI args override every file and source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Merged before every other file and source, so anything overrides them
+    Low,
+    /// The priority of regular config files and [`with_source`](crate::ConfigLoader::with_source)
+    /// sources. Remote configs, the `Cargo.toml` metadata and the config location are
+    /// merged after the files, before the sources.
+    #[default]
+    Normal,
+    /// Merged after every other file and source. The file of
+    /// [`with_config_file_from_env`](crate::ConfigLoader::with_config_file_from_env) comes
+    /// after the other files of this priority.
+    High,
+}
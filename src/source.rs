@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Origins of configuration values.
+
+/// The source a configuration value was loaded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Source {
+    /// A configuration file
+    File,
+    /// A remote or pushed configuration
+    Remote,
+    /// An environment variable
+    Env,
+    /// A systemd credential, see [`with_systemd_credentials`](crate::ConfigLoader::with_systemd_credentials)
+    Credential,
+    /// A command line argument
+    Cli,
+    /// A custom [`ConfigSource`](crate::config_source::ConfigSource)
+    Custom,
+    /// A default filled in after all sources were merged
+    Default,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::File => "file",
+            Self::Remote => "remote",
+            Self::Env => "env",
+            Self::Credential => "credential",
+            Self::Cli => "cli",
+            Self::Custom => "custom",
+            Self::Default => "default",
+        };
+        f.write_str(name)
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Behavior of validators and reload checks on failure.
+
+/// How a failing validator affects the remaining validators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ValidationMode {
+    /// Stop at this validator's failure, together with the failures collected so far
+    #[default]
+    FailFast,
+    /// Record the failure and keep running the remaining validators
+    Collect,
+}
+
+/// What happens when a reload changes an [immutable field](crate::ConfigLoader::with_immutable_fields)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImmutableMode {
+    /// Reject the reload with an [`Error::Validation`](crate::Error::Validation)
+    #[default]
+    Reject,
+    /// Accept the reload, but keep the value of the first load
+    KeepOriginal,
+}
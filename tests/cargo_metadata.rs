@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Config in the `[package.metadata.<tool>]` table of the nearest `Cargo.toml`.
+//!
+//! The current directory is process wide, so everything runs in a single test.
+
+use konfik::{ConfigLoader, Error, Konfik, Priority};
+use std::{
+    fs,
+    sync::{Arc, Mutex},
+};
+
+#[derive(serde::Deserialize, Konfik)]
+struct ToolConfig {
+    max_line_length: u32,
+    #[serde(default)]
+    strict: bool,
+}
+
+const MANIFEST: &str = r#"
+[package]
+name = "app"
+
+[package.metadata.mytool]
+base = { Max_Line_Length = 80 }
+ci = { strict = true }
+
+[package.metadata.other]
+max_line_length = 120
+"#;
+
+#[test]
+fn manifest_table_is_loaded_like_a_config_file() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let crate_dir = dir.path().join("crates").join("app");
+    fs::create_dir_all(dir.path().join(".git"))?;
+    fs::create_dir_all(&crate_dir)?;
+    fs::write(dir.path().join("Cargo.toml"), MANIFEST)?;
+    std::env::set_current_dir(&crate_dir)?;
+
+    let validated = Arc::new(Mutex::new(Vec::new()));
+    let config = ConfigLoader::default()
+        .with_cargo_metadata("mytool")
+        .with_profiles(&["base", "ci"])
+        .with_case_insensitive_keys(true)
+        .with_per_file_validation({
+            let validated = Arc::clone(&validated);
+            move |path, _| {
+                validated
+                    .lock()
+                    .map_err(|e| Error::Validation(e.to_string()))?
+                    .push(path.to_path_buf());
+                Ok(())
+            }
+        })
+        .load::<ToolConfig>()?;
+    assert_eq!(config.max_line_length, 80);
+    assert!(config.strict);
+    assert_eq!(
+        *validated.lock().map_err(|e| e.to_string())?,
+        [dir.path().join("Cargo.toml")]
+    );
+
+    // A higher priority file still overrides the table
+    let file = dir.path().join("tool.toml");
+    fs::write(&file, "max_line_length = 100")?;
+    let config = ConfigLoader::default()
+        .with_cargo_metadata("other")
+        .with_config_layers(vec![(&file, Priority::High)])
+        .load::<ToolConfig>()?;
+    assert_eq!(config.max_line_length, 100);
+
+    let config = ConfigLoader::default()
+        .with_cargo_metadata("other")
+        .load::<ToolConfig>()?;
+    assert_eq!(config.max_line_length, 120);
+
+    // A missing table is skipped
+    let result = ConfigLoader::default()
+        .with_cargo_metadata("missing")
+        .load::<ToolConfig>();
+    assert!(
+        matches!(result, Err(Error::ConfigParse { .. })),
+        "expected max_line_length to be missing"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! The config file picked by an env var is merged after every other file.
+//!
+//! The environment is process wide, so everything runs in a single test.
+
+use konfik::{
+    ConfigLoader, Error, Konfik, Priority, config_meta::FieldMeta, config_source::ConfigSource,
+};
+use serde_json::{Value, json};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    name: String,
+    region: String,
+    port: u16,
+}
+
+struct Store;
+
+impl ConfigSource for Store {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        Ok(json!({ "region": "store", "port": 1 }))
+    }
+}
+
+#[test]
+fn env_file_has_the_highest_file_priority() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let config = dir.path().join("config.toml");
+    let generated = dir.path().join("generated.json");
+    let prod = dir.path().join("prod.toml");
+    fs::write(&config, "name = \"config\"\nregion = \"config\"\nport = 80")?;
+    fs::write(&generated, r#"{ "name": "generated", "port": 9000 }"#)?;
+    fs::write(&prod, "port = 443")?;
+
+    // SAFETY: this is the only test of this binary, nothing reads the environment concurrently
+    #[expect(unsafe_code)]
+    unsafe {
+        std::env::set_var("CONFIG_FILE_FROM_ENV_TEST", &prod);
+    }
+
+    let config = ConfigLoader::default()
+        .with_config_file_from_env("CONFIG_FILE_FROM_ENV_TEST")
+        .with_config_layers(vec![(&generated, Priority::High)])
+        .with_config_file(&config)
+        .with_source(Store)
+        .load::<AppConfig>()?;
+
+    assert_eq!(config.name, "generated");
+    assert_eq!(config.region, "store");
+    assert_eq!(config.port, 443);
+
+    Ok(())
+}
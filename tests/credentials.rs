@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Systemd credentials as a source of their own.
+//!
+//! `CREDENTIALS_DIRECTORY` is process wide, so everything runs in a single test.
+
+use konfik::{ConfigLoader, Error, Konfik, Source};
+use serde_json::json;
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    api_key: String,
+    #[konfik(precedence = "credential")]
+    db_password: String,
+    port: u16,
+}
+
+#[test]
+fn credentials_are_their_own_source() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let credentials = dir.path().join("credentials");
+    fs::create_dir(&credentials)?;
+    fs::write(credentials.join("api-key"), "abc123\n")?;
+    fs::write(credentials.join("db_password"), "hunter2")?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"from-file\"\nport = 80")?;
+
+    // SAFETY: this is the only test of this binary, nothing reads the environment concurrently
+    #[expect(unsafe_code)]
+    unsafe {
+        std::env::set_var("CREDENTIALS_DIRECTORY", &credentials);
+        std::env::set_var("CREDENTIALS_TEST_DB_PASSWORD", "from-env");
+    }
+
+    let loader = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_env_prefix("CREDENTIALS_TEST")
+        .with_systemd_credentials();
+
+    let report = loader.doctor::<AppConfig>();
+    let source = |path| report.field(path).and_then(|field| field.source);
+    assert_eq!(source("api_key"), Some(Source::Credential));
+    assert_eq!(source("db_password"), Some(Source::Credential));
+    assert_eq!(source("port"), Some(Source::File));
+    assert_eq!(Source::Credential.to_string(), "credential");
+
+    // Credentials win over env vars by default, and through `precedence` as well
+    let config = loader.load::<AppConfig>()?;
+    assert_eq!(config.api_key, "abc123");
+    assert_eq!(config.db_password, "hunter2");
+    assert_eq!(config.port, 80);
+
+    // Validators on credentials only see the credentials
+    let Err(err) = loader
+        .with_validation_on(Source::Credential, |credentials| {
+            Err(Error::Validation(credentials.to_string()))
+        })
+        .load::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+    let Error::Validation(message) = err else {
+        panic!("expected a validation error, got {err:?}");
+    };
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&message)?,
+        json!({ "api_key": "abc123", "db_password": "hunter2" })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Config files looked up from the current directory upwards.
+//!
+//! The current directory is process wide, so everything runs in a single test.
+
+use konfik::{ConfigLoader, Konfik};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn ancestor_files_are_found_from_the_current_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let project = dir.path().join("project");
+    let crate_dir = project.join("crates").join("app");
+    fs::create_dir_all(project.join(".git"))?;
+    fs::create_dir_all(&crate_dir)?;
+    // Above the `.git` directory, never searched
+    fs::write(dir.path().join("konfik.toml"), "name = \"outside\"")?;
+    fs::write(project.join("konfik.toml"), "name = \"project\"\nport = 80")?;
+    fs::write(crate_dir.join("konfik.toml"), "port = 8080")?;
+    std::env::set_current_dir(&crate_dir)?;
+
+    let config = ConfigLoader::default()
+        .with_config_file_ancestors("konfik.toml")
+        .load::<AppConfig>()?;
+    assert_eq!(config.name, "project");
+    assert_eq!(config.port, 8080);
+
+    // Only the closest file, which lacks `name`
+    let Err(err) = ConfigLoader::default()
+        .with_config_file_nearest_ancestor("konfik.toml")
+        .load::<AppConfig>()
+    else {
+        panic!("the nearest file has no name");
+    };
+    assert!(err.to_string().contains("name"), "{err}");
+
+    std::env::set_current_dir(&project)?;
+    let config = ConfigLoader::default()
+        .with_config_file_nearest_ancestor("konfik.toml")
+        .load::<AppConfig>()?;
+    assert_eq!(config.name, "project");
+    assert_eq!(config.port, 80);
+
+    Ok(())
+}
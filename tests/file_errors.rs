@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Errors of config files that exist but can't be read or parsed.
+
+use konfik::{ConfigLoader, Error, Konfik};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    api_key: String,
+    port: u16,
+}
+
+#[test]
+fn toml_error_keeps_only_the_offending_line() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\n\nport = [80\n")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>()
+    else {
+        panic!("expected a parse error");
+    };
+
+    let Error::File {
+        path: failed,
+        snippet,
+        span,
+        ..
+    } = &err
+    else {
+        panic!("expected a file error, got {err:?}");
+    };
+    assert_eq!(failed, &path);
+    assert_eq!(snippet, "port = [80");
+    assert!(span.as_ref().is_some_and(|span| span.end <= snippet.len()));
+    assert!(!format!("{err:?}").contains("hunter2"));
+    assert!(!err.to_string().contains("hunter2"));
+    Ok(())
+}
+
+#[test]
+fn json_error_points_into_the_snippet() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, "{\n  \"api_key\": \"hunter2\",\n  \"port\": 80,,\n}")?;
+
+    let Err(Error::File { snippet, span, .. }) = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>()
+    else {
+        panic!("expected a file error");
+    };
+
+    assert_eq!(snippet, "  \"port\": 80,,");
+    let span = span.ok_or("expected a span")?;
+    assert_eq!(&snippet[span.start..], ",");
+    Ok(())
+}
+
+#[test]
+fn error_without_location_has_no_snippet() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, "api_key: hunter2\nport: [80\n")?;
+
+    let Err(Error::File { snippet, span, .. }) = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>()
+    else {
+        panic!("expected a file error");
+    };
+
+    assert!(snippet.is_empty());
+    assert!(span.is_none());
+    Ok(())
+}
+
+#[derive(serde::Deserialize, Konfik)]
+struct DefaultConfig {
+    #[serde(default)]
+    port: u16,
+}
+
+#[test]
+fn malformed_file_is_not_treated_as_missing() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let missing = dir.path().join("missing.toml");
+    let config = ConfigLoader::default()
+        .with_config_file(&missing)
+        .load::<DefaultConfig>()?;
+    assert_eq!(config.port, 0);
+
+    for (name, content) in [
+        ("config.toml", "[server\nport = 8080"),
+        ("config.json", "{ \"port\": 8080"),
+        ("config.yaml", "port: [8080"),
+    ] {
+        let path = dir.path().join(name);
+        fs::write(&path, content)?;
+
+        let Err(Error::File {
+            path: failed,
+            source,
+            ..
+        }) = ConfigLoader::default()
+            .with_config_file(&path)
+            .load::<DefaultConfig>()
+        else {
+            panic!("expected a file error for {name}");
+        };
+        assert_eq!(failed, path);
+        assert!(
+            matches!(*source, Error::Toml(_) | Error::Serde(_) | Error::Yaml(_)),
+            "{name}: {source:?}"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn unreadable_file_is_permission_denied() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\nport = 8080\n")?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o000))?;
+
+    // Root reads the file regardless of its mode
+    if fs::read(&path).is_ok() {
+        return Ok(());
+    }
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>()
+    else {
+        panic!("expected a permission error");
+    };
+
+    let Error::PermissionDenied {
+        path: failed,
+        source,
+    } = &err
+    else {
+        panic!("expected a permission error, got {err:?}");
+    };
+    assert_eq!(failed, &path);
+    assert_eq!(source.kind(), std::io::ErrorKind::PermissionDenied);
+    assert!(err.to_string().contains("check the owner and mode"));
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Explicit file formats and deduplicated config files.
+
+use konfik::{ConfigLoader, Error, FileFormat, Konfik};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    port: u16,
+}
+
+#[test]
+fn format_survives_dedup_of_another_spelling() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join("sub"))?;
+    let path = dir.path().join("app.conf");
+    let other_spelling = dir.path().join("sub").join("..").join("app.conf");
+    fs::write(&path, "port = 8080")?;
+
+    let config = ConfigLoader::default()
+        .with_config_file_as(&other_spelling, FileFormat::Toml)
+        .with_config_file(&path)
+        .load::<AppConfig>()?;
+    assert_eq!(config.port, 8080);
+
+    let config = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_config_file_as(&other_spelling, FileFormat::Toml)
+        .load::<AppConfig>()?;
+    assert_eq!(config.port, 8080);
+    Ok(())
+}
+
+#[test]
+fn unknown_extension_without_format_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("app.conf");
+    fs::write(&path, "port = 8080")?;
+
+    let result = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>();
+
+    assert!(matches!(result, Err(Error::ParseFileFormat(_))));
+    Ok(())
+}
+
+#[test]
+fn explicit_format_wins_over_extension() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("app.json");
+    fs::write(&path, "port: 8080")?;
+
+    let config = ConfigLoader::default()
+        .with_config_file_as(&path, FileFormat::Yaml)
+        .load::<AppConfig>()?;
+    assert_eq!(config.port, 8080);
+
+    let result = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>();
+    assert!(matches!(result, Err(Error::File { .. })));
+    Ok(())
+}
+
+#[test]
+fn duplicate_file_is_merged_at_its_last_position() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.toml");
+    let local = dir.path().join("local.toml");
+    fs::write(&base, "port = 1")?;
+    fs::write(&local, "port = 2")?;
+
+    let config = ConfigLoader::default()
+        .with_config_files(vec![&base, &local, &dir.path().join(".").join("base.toml")])
+        .load::<AppConfig>()?;
+
+    assert_eq!(config.port, 1);
+    Ok(())
+}
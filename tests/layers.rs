@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Where explicitly layered config files and prioritized sources are merged relative to
+//! the other sources.
+
+use konfik::{
+    ConfigLoader, Error, Konfik, Priority, config_meta::FieldMeta, config_source::ConfigSource,
+};
+use serde_json::{Value, json};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    name: String,
+    region: String,
+    port: u16,
+}
+
+struct Store;
+
+impl ConfigSource for Store {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        Ok(json!({ "region": "store", "port": 1 }))
+    }
+}
+
+#[test]
+fn high_layers_override_every_other_file_and_source() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.yaml");
+    let config = dir.path().join("config.toml");
+    let generated = dir.path().join("generated.json");
+    fs::write(&base, "name: base\nregion: base\nport: 80")?;
+    fs::write(&config, "name = \"config\"")?;
+    fs::write(&generated, r#"{ "port": 9000 }"#)?;
+
+    let loader = ConfigLoader::default()
+        .with_config_layers(vec![(&generated, Priority::High), (&base, Priority::Low)])
+        .with_config_file(&config)
+        .with_source(Store);
+    let config = loader.load::<AppConfig>()?;
+
+    assert_eq!(config.name, "config");
+    assert_eq!(config.region, "store");
+    assert_eq!(config.port, 9000);
+
+    // A file listed again as a High layer is merged at that position
+    let config = loader
+        .with_config_file(&generated)
+        .with_config_layers(vec![(&base, Priority::High)])
+        .load::<AppConfig>()?;
+    assert_eq!(config.name, "base");
+    assert_eq!(config.region, "base");
+    assert_eq!(config.port, 80);
+
+    Ok(())
+}
+
+#[test]
+fn numeric_priorities_sort_all_files() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let fallback = dir.path().join("fallback.toml");
+    let config = dir.path().join("config.toml");
+    let site = dir.path().join("site.json");
+    fs::write(
+        &fallback,
+        "name = \"fallback\"\nregion = \"fallback\"\nport = 1",
+    )?;
+    fs::write(&config, "region = \"config\"\nport = 2")?;
+    fs::write(&site, r#"{ "port": 3 }"#)?;
+
+    let config = ConfigLoader::default()
+        .with_config_file_prio(&site, 10)
+        .with_config_file(&config)
+        .with_config_file_prio(&fallback, -10)
+        .load::<AppConfig>()?;
+
+    assert_eq!(config.name, "fallback");
+    assert_eq!(config.region, "config");
+    assert_eq!(config.port, 3);
+    Ok(())
+}
+
+#[test]
+fn files_and_sources_share_the_priorities() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.toml");
+    let generated = dir.path().join("generated.json");
+    fs::write(&base, "name = \"base\"\nregion = \"base\"\nport = 80")?;
+    fs::write(
+        &generated,
+        r#"{ "name": "generated", "region": "generated" }"#,
+    )?;
+
+    let loader = ConfigLoader::default()
+        .with_env_prefix("LAYERS")
+        .with_env_vars([("LAYERS_NAME", "env")])
+        .with_config_layers(vec![(&generated, Priority::High)]);
+
+    // Files are merged before sources of the same priority
+    let config = loader
+        .clone()
+        .with_source_priority(Store, Priority::High)
+        .with_config_layers(vec![(&base, Priority::Low)])
+        .load::<AppConfig>()?;
+    assert_eq!(config.region, "store");
+    assert_eq!(config.port, 1);
+    // Env vars override every file and source
+    assert_eq!(config.name, "env");
+
+    // Lower priorities are merged first, whatever kind they are
+    let config = loader
+        .with_source_priority(Store, Priority::Low)
+        .with_config_layers(vec![(&base, Priority::Normal)])
+        .load::<AppConfig>()?;
+    assert_eq!(config.region, "generated");
+    assert_eq!(config.port, 80);
+    Ok(())
+}
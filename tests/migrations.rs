@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Migrations of old config files to the current version.
+
+use konfik::{ConfigLoader, Error, Konfik};
+use serde_json::json;
+use std::fs;
+
+const fn current_version() -> u64 {
+    2
+}
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    #[serde(default = "current_version")]
+    #[konfik(eval_default)]
+    version: u64,
+    #[serde(default = "default_url")]
+    #[konfik(eval_default)]
+    db_url: String,
+}
+
+fn default_url() -> String {
+    "postgres://localhost".to_string()
+}
+
+/// v1 -> v2: `db` was renamed to `db_url`
+fn loader(path: &std::path::Path) -> ConfigLoader {
+    ConfigLoader::default()
+        .with_config_file(path)
+        .with_migration(1, |mut config| {
+            // Files setting both keep the new one
+            if let Some(map) = config.as_object_mut()
+                && let Some(url) = map.remove("db")
+            {
+                map.entry("db_url").or_insert(url);
+            }
+            Ok(config)
+        })
+}
+
+#[test]
+fn unversioned_file_is_migrated_despite_version_default() -> Result<(), Box<dyn std::error::Error>>
+{
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, r#"{ "db": "postgres://old" }"#)?;
+
+    let config = loader(&path).load::<AppConfig>()?;
+
+    assert_eq!(config.version, 2);
+    assert_eq!(config.db_url, "postgres://old");
+    Ok(())
+}
+
+#[test]
+fn migrated_key_is_not_shadowed_by_its_default() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, r#"{ "version": 1, "db": "postgres://old" }"#)?;
+
+    let merged = loader(&path).load_merged::<AppConfig>()?;
+
+    assert_eq!(merged["db_url"], json!("postgres://old"));
+    assert_eq!(merged.get("db"), None);
+    Ok(())
+}
+
+#[test]
+fn current_file_is_left_alone() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, r#"{ "version": 2, "db": "ignored" }"#)?;
+
+    let config = loader(&path).load::<AppConfig>()?;
+
+    assert_eq!(config.db_url, "postgres://localhost");
+    Ok(())
+}
+
+#[test]
+fn invalid_version_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, r#"{ "version": "two" }"#)?;
+
+    let result = loader(&path).load::<AppConfig>();
+
+    assert!(matches!(result, Err(Error::Validation(message)) if message.contains("version")));
+    Ok(())
+}
+
+#[test]
+fn failing_migration_names_its_version() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    fs::write(&path, "{}")?;
+
+    let result = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_migration(1, |_| Err(Error::Validation("no db".to_string())))
+        .load::<AppConfig>();
+
+    assert!(matches!(result, Err(Error::Validation(message)) if message.contains("from v1")));
+    Ok(())
+}
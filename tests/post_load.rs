@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Post load hooks and the types they accept.
+
+use konfik::{ConfigLoader, Error, Konfik};
+use std::{
+    marker::PhantomData,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+#[derive(serde::Deserialize, Konfik)]
+struct AppConfig {
+    #[serde(default)]
+    level: String,
+}
+
+#[derive(serde::Deserialize, Konfik)]
+struct OtherConfig {
+    #[serde(default)]
+    port: u16,
+}
+
+#[derive(serde::Deserialize, Konfik)]
+struct GenericConfig<T> {
+    #[serde(default)]
+    value: Option<T>,
+}
+
+#[test]
+fn only_hooks_of_the_loaded_type_run() -> Result<(), Error> {
+    let app_calls = Arc::new(AtomicUsize::new(0));
+    let other_calls = Arc::new(AtomicUsize::new(0));
+    let (app, other) = (Arc::clone(&app_calls), Arc::clone(&other_calls));
+
+    let loader = ConfigLoader::default()
+        .with_post_load(move |config: &AppConfig| {
+            assert!(config.level.is_empty());
+            app.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        })
+        .with_post_load(move |config: &OtherConfig| {
+            assert_eq!(config.port, 0);
+            other.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        });
+
+    loader.load::<AppConfig>()?;
+    loader.load::<AppConfig>()?;
+    loader.load::<OtherConfig>()?;
+
+    assert_eq!(app_calls.load(Ordering::Relaxed), 2);
+    assert_eq!(other_calls.load(Ordering::Relaxed), 1);
+    Ok(())
+}
+
+#[test]
+fn hooks_of_other_types_are_skipped() -> Result<(), Error> {
+    let config = ConfigLoader::default()
+        .with_post_load(|_: &OtherConfig| Err(Error::Validation("wrong hook".into())))
+        .load::<AppConfig>()?;
+
+    assert!(config.level.is_empty());
+    Ok(())
+}
+
+#[test]
+fn hook_errors_abort_the_load() {
+    let result = ConfigLoader::default()
+        .with_post_load(|_: &AppConfig| Err(Error::Validation("logger failed".into())))
+        .load::<AppConfig>();
+
+    assert!(matches!(result, Err(Error::Validation(message)) if message == "logger failed"));
+}
+
+#[test]
+fn generic_configs_load_without_hooks() -> Result<(), Error> {
+    // Configs don't have to be `'static` to load
+    fn load_borrowed<'a>() -> Result<GenericConfig<PhantomData<&'a str>>, Error> {
+        ConfigLoader::default().load()
+    }
+    assert!(load_borrowed()?.value.is_none());
+
+    // Hooks can't accept generic configs, so the hooks of other types are skipped
+    let config = ConfigLoader::default()
+        .with_post_load(|_: &AppConfig| Err(Error::Validation("wrong hook".into())))
+        .load::<GenericConfig<u8>>()?;
+    assert!(config.value.is_none());
+    Ok(())
+}
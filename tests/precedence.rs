@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Which source wins for every combination of files, env vars and CLI args.
+
+use konfik::{ConfigLoader, Konfik, Nested};
+use std::fs;
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize, Konfik, clap::Parser)]
+struct Config {
+    #[arg(long)]
+    port: Option<String>,
+    #[konfik(precedence = "file")]
+    #[arg(long)]
+    region: Option<String>,
+    #[konfik(nested)]
+    #[serde(default)]
+    #[command(flatten)]
+    db: Database,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, serde::Deserialize, Nested, clap::Args)]
+struct Database {
+    #[arg(long)]
+    host: Option<String>,
+}
+
+#[test]
+fn precedence_matrix() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let with_file = dir.path().join("with.toml");
+    let without_file = dir.path().join("without.toml");
+    fs::write(
+        &with_file,
+        "port = \"file\"\nregion = \"file\"\n[db]\nhost = \"file\"",
+    )?;
+    fs::write(&without_file, "")?;
+
+    let env_vars = [
+        ("PRECEDENCE_MATRIX_PORT", "env"),
+        ("PRECEDENCE_MATRIX_REGION", "env"),
+        ("PRECEDENCE_MATRIX_DB_HOST", "env"),
+    ];
+
+    for file in [false, true] {
+        for env in [false, true] {
+            for cli in [false, true] {
+                let mut args = vec!["myapp"];
+                if cli {
+                    args.extend(["--port", "cli", "--region", "cli", "--host", "cli"]);
+                }
+                let config = ConfigLoader::default()
+                    .with_config_file(if file { &with_file } else { &without_file })
+                    .with_env_prefix("PRECEDENCE_MATRIX")
+                    .with_env_vars(if env { &env_vars[..] } else { &[] }.iter().copied())
+                    .with_cli_args(args)
+                    .load_with_cli::<Config>()?;
+
+                // CLI args > env vars > files
+                let winner = [(cli, "cli"), (env, "env"), (file, "file")]
+                    .into_iter()
+                    .find_map(|(set, source)| set.then(|| source.to_string()));
+                // `precedence = "file"` puts the file first, the others keep their order
+                let region = if file {
+                    Some("file".into())
+                } else {
+                    winner.clone()
+                };
+
+                assert_eq!(
+                    config,
+                    Config {
+                        port: winner.clone(),
+                        region,
+                        db: Database { host: winner },
+                    },
+                    "file: {file}, env: {env}, cli: {cli}"
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
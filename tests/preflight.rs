@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Readiness checks of the required fields against config files, env vars and defaults.
+
+use konfik::{ConfigLoader, Error, Konfik};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    database_url: String,
+    #[konfik(env = "API_TOKEN")]
+    api_key: String,
+    #[serde(default)]
+    port: u16,
+}
+
+#[test]
+fn env_vars_and_defaults_provide_fields() -> Result<(), Error> {
+    ConfigLoader::default()
+        .with_env_prefix("APP")
+        .with_env_vars([
+            ("APP_DATABASE_URL", "postgres://db"),
+            ("APP_API_TOKEN", "hunter2"),
+        ])
+        .preflight::<AppConfig>()
+}
+
+#[test]
+fn fields_without_env_vars_are_missing() {
+    let result = ConfigLoader::default()
+        .with_env_prefix("APP")
+        // The derived name isn't read for fields with their own env vars
+        .with_env_vars([("APP_API_KEY", "hunter2")])
+        .with_config_file("does/not/exist.toml")
+        .preflight::<AppConfig>();
+
+    let Err(Error::MissingRequired(missing)) = result else {
+        panic!("expected missing fields, got {result:?}");
+    };
+    assert_eq!(missing, ["api_key", "database_url"]);
+}
+
+#[test]
+fn files_missing_a_required_key_are_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "database_url = \"postgres://db\"")?;
+
+    let result = ConfigLoader::default()
+        .with_env_vars::<_, String, String>([])
+        .with_config_file(&path)
+        .preflight::<AppConfig>();
+
+    let Err(Error::MissingRequired(missing)) = result else {
+        panic!("expected missing fields, got {result:?}");
+    };
+    assert_eq!(missing, ["api_key"]);
+
+    fs::write(
+        &path,
+        "database_url = \"postgres://db\"\napi_key = \"hunter2\"",
+    )?;
+    ConfigLoader::default()
+        .with_env_vars::<_, String, String>([])
+        .with_config_file(&path)
+        .preflight::<AppConfig>()?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! `--print-config` prints the values `load` would produce.
+//!
+//! Printing exits the process, so the test runs itself again as a child process.
+
+use konfik::{ConfigLoader, Konfik};
+use std::{env, fs, process::Command};
+
+/// Set in the child process to the directory of its config file
+const CHILD: &str = "KONFIK_PRINT_CONFIG_CHILD";
+
+#[derive(serde::Deserialize, Konfik, clap::Parser)]
+struct AppConfig {
+    #[konfik(precedence = "file")]
+    #[arg(long)]
+    region: String,
+    #[arg(long)]
+    port: u16,
+    #[konfik(default_if_none = "4")]
+    #[arg(long)]
+    workers: Option<u8>,
+}
+
+#[test]
+fn printed_config_applies_precedence_and_defaults() -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = env::var_os(CHILD) {
+        let path = std::path::Path::new(&dir).join("config.toml");
+        ConfigLoader::default()
+            .with_config_file(&path)
+            .with_print_config_flag(true)
+            .with_cli_args([
+                "myapp",
+                "--region",
+                "cli",
+                "--port",
+                "8080",
+                "--print-config",
+                "--print-config-format",
+                "json",
+            ])
+            .load_with_cli::<AppConfig>()?;
+        panic!("--print-config should exit the process");
+    }
+
+    let dir = tempfile::tempdir()?;
+    fs::write(
+        dir.path().join("config.toml"),
+        "region = \"file\"\nport = 80",
+    )?;
+
+    let output = Command::new(env::current_exe()?)
+        .args([
+            "--exact",
+            "printed_config_applies_precedence_and_defaults",
+            "--nocapture",
+        ])
+        .env(CHILD, dir.path())
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let start = stdout.find('{').ok_or("no config printed")?;
+    let end = stdout.rfind('}').ok_or("no config printed")?;
+    let printed = serde_json::from_str::<serde_json::Value>(&stdout[start..=end])?;
+    assert_eq!(
+        printed,
+        serde_json::json!({ "region": "file", "port": 8080, "workers": 4 })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn printed_config_hides_secrets() -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = env::var_os(CHILD) {
+        let path = std::path::Path::new(&dir).join("config.toml");
+        ConfigLoader::default()
+            .with_config_file(&path)
+            .with_secret_resolver(|_| Some("hunter2".to_string()))
+            .with_print_config_flag(true)
+            .with_cli_args(["myapp", "--print-config", "--print-config-format", "json"])
+            .load_with_cli::<AppConfig>()?;
+        panic!("--print-config should exit the process");
+    }
+
+    let dir = tempfile::tempdir()?;
+    fs::write(
+        dir.path().join("config.toml"),
+        "region = \"secret://region\"\nport = 80",
+    )?;
+
+    let output = Command::new(env::current_exe()?)
+        .args(["--exact", "printed_config_hides_secrets", "--nocapture"])
+        .env(CHILD, dir.path())
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("secret://region"), "{stdout}");
+    assert!(stdout.contains(r#""region": "[REDACTED]""#), "{stdout}");
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Scrubbing the values at redacted paths from errors.
+
+use konfik::{ConfigLoader, Error, Konfik, config_meta::FieldMeta, config_source::ConfigSource};
+use serde_json::Value;
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    api_key: String,
+    retries: u32,
+}
+
+/// A source that fails, echoing a value some earlier source provided.
+struct FailingSource;
+
+impl ConfigSource for FailingSource {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        Err(Error::Remote("rejected key hunter2".to_string()))
+    }
+}
+
+#[test]
+fn overridden_value_is_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.toml");
+    let local = dir.path().join("local.toml");
+    fs::write(&base, "api_key = \"hunter2\"\nretries = 3")?;
+    fs::write(&local, "api_key = \"correct-horse\"\nretries = 3")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_files(vec![&base, &local])
+        .with_redacted_paths(&["api_key"])
+        .with_validation(|_| Err(Error::Validation("hunter2 and correct-horse".to_string())))
+        .load::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "Validation error: [REDACTED] and [REDACTED]"
+    );
+    Ok(())
+}
+
+#[test]
+fn errors_of_later_sources_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\nretries = 3")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_source(FailingSource)
+        .with_redacted_paths(&["api_key"])
+        .load::<AppConfig>()
+    else {
+        panic!("the source always fails");
+    };
+
+    assert_eq!(err.to_string(), "Remote error: rejected key [REDACTED]");
+    Ok(())
+}
+
+#[test]
+fn per_file_validator_errors_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\nretries = 3")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["api_key"])
+        .with_per_file_validation(|_, config| {
+            Err(Error::Validation(format!("bad key {}", config["api_key"])))
+        })
+        .load::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+
+    let Error::InvalidFile { source, .. } = &err else {
+        panic!("expected an invalid file, got {err:?}");
+    };
+    assert_eq!(
+        source.to_string(),
+        "Validation error: bad key \"[REDACTED]\""
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_errors_of_files_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let base = dir.path().join("base.toml");
+    let broken = dir.path().join("broken.toml");
+    fs::write(&base, "api_key = \"hunter2\"\nretries = 3")?;
+    fs::write(&broken, "api_key = \"hunter2\n")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_files(vec![&base, &broken])
+        .with_redacted_paths(&["api_key"])
+        .load::<AppConfig>()
+    else {
+        panic!("expected a parse error");
+    };
+
+    let Error::File { snippet, span, .. } = &err else {
+        panic!("expected a file error, got {err:?}");
+    };
+    assert_eq!(snippet, "api_key = \"[REDACTED]");
+    assert!(span.is_none());
+    assert!(!format!("{err:?}").contains("hunter2"));
+    assert!(!err.to_string().contains("hunter2"));
+    Ok(())
+}
+
+#[test]
+fn numbers_are_not_scrubbed_from_messages() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\nretries = 1")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["retries"])
+        .with_validation(|_| Err(Error::Validation("error at line 1, column 12".to_string())))
+        .load::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "Validation error: error at line 1, column 12"
+    );
+    Ok(())
+}
+
+#[test]
+fn collected_validation_errors_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"hunter2\"\nretries = 3")?;
+
+    let Err(Error::Multiple(errors)) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["api_key"])
+        .with_validation_mode(konfik::ValidationMode::Collect, |config| {
+            Err(Error::Validation(format!("first {}", config["api_key"])))
+        })
+        .with_validation_mode(konfik::ValidationMode::Collect, |_| {
+            Err(Error::Validation("second hunter2".to_string()))
+        })
+        .load::<AppConfig>()
+    else {
+        panic!("expected both validators to fail");
+    };
+
+    let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "Validation error: first \"[REDACTED]\"",
+            "Validation error: second [REDACTED]"
+        ]
+    );
+    Ok(())
+}
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct PinConfig {
+    pin: u32,
+    #[konfik(size)]
+    #[serde(default)]
+    cache: u64,
+}
+
+#[test]
+fn numeric_values_are_redacted_from_parse_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "pin = 98765432109")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["pin"])
+        .load::<PinConfig>()
+    else {
+        panic!("the pin doesn't fit a u32");
+    };
+
+    assert!(matches!(err, Error::ConfigParse { .. }), "{err:?}");
+    assert!(err.to_string().contains("pin: [REDACTED]"), "{err}");
+    assert!(!format!("{err} {err:?}").contains("98765432109"), "{err:?}");
+    Ok(())
+}
+
+#[test]
+fn field_errors_leave_out_redacted_values() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "pin = 1234\ncache = \"12 parsecs\"")?;
+
+    let loader = ConfigLoader::default().with_config_file(&path);
+    let Err(Error::Validation(message)) = loader.load::<PinConfig>() else {
+        panic!("expected an invalid size");
+    };
+    assert_eq!(message, "invalid size for cache: \"12 parsecs\"");
+
+    let Err(Error::Validation(message)) =
+        loader.with_redacted_paths(&["cache"]).load::<PinConfig>()
+    else {
+        panic!("expected an invalid size");
+    };
+    assert_eq!(message, "invalid size for cache: [REDACTED]");
+    Ok(())
+}
+
+#[test]
+fn short_values_only_replace_whole_words() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"on\"\nretries = 3")?;
+
+    let Err(err) = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["api_key"])
+        .with_validation(|_| Err(Error::Validation("connection refused: on".to_string())))
+        .load::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "Validation error: connection refused: [REDACTED]"
+    );
+    Ok(())
+}
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! References between config values that never resolve.
+
+use konfik::{ConfigLoader, Error, Konfik};
+use std::fs;
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    a: String,
+    b: String,
+}
+
+fn load(config: &str) -> Result<Result<AppConfig, Error>, Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, config)?;
+
+    Ok(ConfigLoader::default()
+        .with_config_file(&path)
+        .with_value_references()
+        .load::<AppConfig>())
+}
+
+#[test]
+fn mutual_references_are_a_cycle() -> Result<(), Box<dyn std::error::Error>> {
+    let result = load("a = \"${b}\"\nb = \"x-${a}\"\n")?;
+
+    let Err(Error::Validation(message)) = &result else {
+        panic!("expected a reference cycle, got {result:?}");
+    };
+    assert_eq!(message, "reference cycle: b -> a -> b");
+    Ok(())
+}
+
+#[test]
+fn self_references_are_a_cycle() -> Result<(), Box<dyn std::error::Error>> {
+    let result = load("a = \"${a}\"\nb = \"b\"\n")?;
+
+    let Err(Error::Validation(message)) = &result else {
+        panic!("expected a reference cycle, got {result:?}");
+    };
+    assert_eq!(message, "reference cycle: a -> a");
+    Ok(())
+}
+
+#[test]
+fn repeated_references_are_not_a_cycle() -> Result<(), Box<dyn std::error::Error>> {
+    let config = load("a = \"${b}/${b}\"\nb = \"x\"\n")??;
+
+    assert_eq!(config.a, "x/x");
+    Ok(())
+}
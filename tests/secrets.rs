@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Resolving `secret://` references and keeping the resolved secrets out of errors.
+
+use konfik::{ConfigLoader, Error, FileFormat, Konfik};
+use serde_json::json;
+use std::{fs, path::Path};
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    api_key: u64,
+}
+
+fn loader(path: &Path) -> ConfigLoader {
+    ConfigLoader::default()
+        .with_config_file(path)
+        .with_redacted_paths(&["api_key"])
+        .with_secret_resolver(|name| (name == "k").then(|| "hunter2".to_string()))
+}
+
+#[test]
+fn resolved_secret_is_redacted_from_parse_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"secret://k\"")?;
+
+    let Err(err) = loader(&path).load::<AppConfig>() else {
+        panic!("a string can't be deserialized into u64");
+    };
+
+    assert!(matches!(err, Error::ConfigParse { .. }));
+    let message = err.to_string();
+    assert!(!message.contains("hunter2"), "{message}");
+    assert!(message.contains("[REDACTED]"), "{message}");
+    Ok(())
+}
+
+#[test]
+fn resolved_secret_is_redacted_from_validation_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"secret://k\"")?;
+
+    let Err(err) = loader(&path)
+        .with_validation(|config| Err(Error::Validation(format!("rejected {}", config["api_key"]))))
+        .load_merged::<AppConfig>()
+    else {
+        panic!("the validator always fails");
+    };
+
+    assert_eq!(err.to_string(), "Validation error: rejected \"[REDACTED]\"");
+    Ok(())
+}
+
+#[test]
+fn unresolved_secret_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "api_key = \"secret://missing\"")?;
+
+    let result = loader(&path).load::<AppConfig>();
+
+    assert!(
+        matches!(result, Err(Error::Validation(message)) if message.contains("`missing` at api_key"))
+    );
+    Ok(())
+}
+
+#[test]
+fn secrets_are_resolved_in_nested_values() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(serde::Deserialize, Konfik)]
+    struct Config {
+        passwords: Vec<String>,
+    }
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "passwords = [\"plain\", \"secret://db\"]")?;
+
+    let config = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_secret_resolver(|name| Some(format!("resolved-{name}")))
+        .load::<Config>()?;
+
+    assert_eq!(config.passwords, ["plain", "resolved-db"]);
+    Ok(())
+}
+
+#[derive(serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct DbConfig {
+    user: String,
+    password: String,
+}
+
+/// Loader resolving `secret://db` without any redacted paths
+fn db_loader(path: &Path) -> ConfigLoader {
+    ConfigLoader::default()
+        .with_config_file(path)
+        .with_secret_resolver(|name| (name == "db").then(|| "hunter2".to_string()))
+}
+
+#[test]
+fn resolved_secret_is_redacted_in_doctor() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "user = \"app\"\npassword = \"secret://db\"")?;
+
+    let report = db_loader(&path).doctor::<DbConfig>();
+
+    let password = report.field("password").ok_or("no password field")?;
+    assert_eq!(password.value, Some(json!("[REDACTED]")));
+    let user = report.field("user").ok_or("no user field")?;
+    assert_eq!(user.value, Some(json!("app")));
+    assert!(!report.to_string().contains("hunter2"));
+    Ok(())
+}
+
+#[test]
+fn resolved_secret_is_redacted_in_dump() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "user = \"app\"\npassword = \"secret://db\"")?;
+
+    let (config, dump) = db_loader(&path).load_and_dump::<DbConfig>(FileFormat::Json)?;
+
+    assert_eq!(config.password, "hunter2");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&dump)?,
+        json!({ "user": "app", "password": "[REDACTED]" })
+    );
+    Ok(())
+}
+
+#[test]
+fn resolved_secrets_in_arrays_are_redacted_in_dump() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(serde::Deserialize, Konfik)]
+    struct Config {
+        passwords: Vec<String>,
+    }
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "passwords = [\"plain\", \"secret://db\"]")?;
+
+    let (config, dump) = db_loader(&path).load_and_dump::<Config>(FileFormat::Json)?;
+
+    assert_eq!(config.passwords, ["plain", "hunter2"]);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&dump)?,
+        json!({ "passwords": ["plain", "[REDACTED]"] })
+    );
+    Ok(())
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Human readable sizes at the edges of `u64`.
+
+use konfik::{ConfigLoader, Error, Konfik};
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+struct AppConfig {
+    #[konfik(size)]
+    cache: u64,
+}
+
+fn load(cache: &str) -> Result<AppConfig, Error> {
+    ConfigLoader::default()
+        .with_env_vars([("CACHE", cache)])
+        .load::<AppConfig>()
+}
+
+#[test]
+fn fractional_sizes_are_rounded() -> Result<(), Error> {
+    assert_eq!(load("1.5 KiB")?.cache, 1536);
+    assert_eq!(load("0.0005KB")?.cache, 1);
+    Ok(())
+}
+
+#[test]
+fn sizes_above_u64_max_are_rejected() {
+    for cache in [
+        "16384 PiB",
+        "20000000 TB",
+        "20000000.5 TB",
+        "18446744073709551616 B",
+    ] {
+        let result = load(cache);
+        assert!(
+            matches!(&result, Err(Error::Validation(message)) if message.starts_with("invalid size for cache")),
+            "{cache}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn non_finite_sizes_are_rejected() {
+    let huge = format!("{}.5 B", "9".repeat(400));
+    let result = load(&huge);
+    assert!(
+        matches!(&result, Err(Error::Validation(message)) if message.starts_with("invalid size for cache")),
+        "{result:?}"
+    );
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Stream updates go through the same sources and redaction as a regular load.
+
+use konfik::{
+    ConfigLoader, Error, ImmutableMode, Konfik, Priority,
+    config_meta::FieldMeta,
+    config_source::{ChannelSource, ConfigSource},
+};
+use serde_json::{Value, json};
+use std::{fs, sync::mpsc};
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+struct AppConfig {
+    port: u16,
+    region: String,
+    api_key: String,
+}
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+struct ServerConfig {
+    #[serde(default)]
+    port: u16,
+    #[serde(default)]
+    workers: u8,
+}
+
+/// Pins the region above every other source
+struct Pinned;
+
+impl ConfigSource for Pinned {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        Ok(json!({ "region": "pinned" }))
+    }
+}
+
+/// Fails with a message that echoes the API key of the config file
+struct Failing;
+
+impl ConfigSource for Failing {
+    fn load(&self, _fields: &[FieldMeta]) -> Result<Value, Error> {
+        Err(Error::Remote("rejected api key hunter2".into()))
+    }
+}
+
+fn updates(updates: &[Value]) -> Result<ChannelSource, mpsc::SendError<Value>> {
+    let (sender, receiver) = mpsc::channel();
+    for update in updates {
+        sender.send(update.clone())?;
+    }
+    Ok(ChannelSource::new(receiver))
+}
+
+#[test]
+fn updates_are_merged_below_high_sources() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 80\nregion = \"file\"\napi_key = \"hunter2\"")?;
+
+    let loader = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_source_priority(Pinned, Priority::High);
+    let configs = loader
+        .subscribe::<AppConfig, _>(updates(&[
+            json!({ "port": 8080, "region": "update" }),
+            json!({ "port": 9090 }),
+        ])?)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let values = configs
+        .iter()
+        .map(|config| (config.port, config.region.as_str(), config.api_key.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [(8080, "pinned", "hunter2"), (9090, "pinned", "hunter2")]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn source_errors_are_redacted() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "port = 80\nregion = \"file\"\napi_key = \"hunter2\"")?;
+
+    let loader = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_redacted_paths(&["api_key"])
+        .with_source_priority(Failing, Priority::High);
+    let mut configs = loader.subscribe::<AppConfig, _>(updates(&[json!({ "port": 8080 })])?);
+
+    let Some(Err(err)) = configs.next() else {
+        panic!("the failing source should fail the update");
+    };
+    assert!(!format!("{err} {err:?}").contains("hunter2"), "{err:?}");
+    assert!(err.to_string().contains("[REDACTED]"), "{err}");
+    assert!(configs.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn kept_fields_absent_from_the_first_config_stay_absent() -> Result<(), Box<dyn std::error::Error>>
+{
+    let loader =
+        ConfigLoader::default().with_immutable_fields(&["port"], ImmutableMode::KeepOriginal);
+    let configs = loader
+        .subscribe::<ServerConfig, _>(updates(&[
+            json!({ "workers": 2 }),
+            json!({ "port": 9, "workers": 4 }),
+        ])?)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let values = configs
+        .iter()
+        .map(|config| (config.port, config.workers))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [(0, 2), (0, 4)]);
+
+    Ok(())
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn kept_fields_absent_from_the_first_file_stay_absent() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "workers = 2")?;
+
+    let loader = ConfigLoader::default()
+        .with_config_file(&path)
+        .with_immutable_fields(&["port"], ImmutableMode::KeepOriginal);
+    let mut configs = loader.watch::<ServerConfig>()?;
+    let first = configs.next().transpose()?.ok_or("no config")?;
+    assert_eq!((first.port, first.workers), (0, 2));
+
+    let writer = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        fs::write(&path, "port = 9\nworkers = 4")
+    });
+    let reloaded = configs.next().transpose()?.ok_or("no reload")?;
+    writer.join().map_err(|_| "writer panicked")??;
+    assert_eq!((reloaded.port, reloaded.workers), (0, 4));
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! Failures of the Unix domain socket source.
+
+#![cfg(unix)]
+
+use konfik::{ConfigLoader, Error, Konfik, config_source::UnixSocketSource};
+use std::{
+    io::Write,
+    os::unix::net::UnixListener,
+    path::Path,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+#[derive(Debug, serde::Deserialize, Konfik)]
+#[expect(dead_code)]
+struct AppConfig {
+    port: u16,
+}
+
+/// Answers a single connection to `path` with `response`, then keeps it open for `hold`
+fn agent(
+    path: &Path,
+    response: &'static [u8],
+    hold: Duration,
+) -> std::io::Result<JoinHandle<std::io::Result<()>>> {
+    let listener = UnixListener::bind(path)?;
+    Ok(thread::spawn(move || {
+        let (mut stream, _) = listener.accept()?;
+        stream.write_all(response)?;
+        thread::sleep(hold);
+        Ok(())
+    }))
+}
+
+fn remote_error(source: UnixSocketSource) -> String {
+    let Err(err) = ConfigLoader::default()
+        .with_source(source)
+        .load::<AppConfig>()
+    else {
+        panic!("loading from the socket should fail");
+    };
+    let Error::Remote(message) = err else {
+        panic!("expected a remote error, got {err:?}");
+    };
+    message
+}
+
+#[test]
+fn missing_socket() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("missing.sock");
+
+    let message = remote_error(UnixSocketSource::new(&path));
+    assert!(
+        message.starts_with(&path.display().to_string()),
+        "{message}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn invalid_responses() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+
+    for (name, response, expected) in [
+        (
+            "invalid.sock",
+            &b"port = 8080"[..],
+            "the response is not valid JSON",
+        ),
+        ("array.sock", b"[8080]", "the response is not a JSON object"),
+    ] {
+        let path = dir.path().join(name);
+        let agent = agent(&path, response, Duration::ZERO)?;
+
+        let message = remote_error(UnixSocketSource::new(&path));
+        assert!(message.contains(expected), "{message}");
+        agent.join().map_err(|_| "agent panicked")??;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn timeout() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("slow.sock");
+    // Never closes the connection within the timeout
+    let agent = agent(&path, b"{", Duration::from_millis(500))?;
+
+    let message = remote_error(UnixSocketSource::new(&path).timeout(Duration::from_millis(50)));
+    assert!(
+        message.starts_with(&path.display().to_string()),
+        "{message}"
+    );
+    agent.join().map_err(|_| "agent panicked")??;
+
+    Ok(())
+}
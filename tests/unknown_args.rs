@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! CLI args the config type doesn't know, with `with_ignore_unknown_args`.
+//!
+//! Usage errors exit the process, so those tests run themselves again as a child process.
+
+use konfik::{ConfigLoader, Konfik};
+use std::{env, fs, path::Path, process::Command};
+
+/// Set in the child process to the port passed on the CLI
+const CHILD_PORT: &str = "KONFIK_UNKNOWN_ARGS_PORT";
+/// Set in the child process to the directory of its config file
+const CHILD_DIR: &str = "KONFIK_UNKNOWN_ARGS_DIR";
+
+#[derive(serde::Deserialize, Konfik, clap::Parser)]
+struct AppConfig {
+    #[arg(long)]
+    port: u16,
+    #[serde(default)]
+    #[arg(short, long)]
+    verbose: bool,
+}
+
+fn load(dir: &Path, args: &[&str]) -> Result<AppConfig, konfik::Error> {
+    let path = dir.join("config.toml");
+    ConfigLoader::default()
+        .with_config_file(&path)
+        .with_cli_args(args)
+        .with_ignore_unknown_args(true)
+        .load_with_cli::<AppConfig>()
+}
+
+#[test]
+fn unknown_args_are_skipped() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    fs::write(dir.path().join("config.toml"), "port = 80")?;
+
+    let config = load(
+        dir.path(),
+        &[
+            "myapp",
+            "--tracing-level",
+            "debug",
+            "--port",
+            "8080",
+            "--color=always",
+            "-x",
+            "-v",
+        ],
+    )?;
+    assert_eq!(config.port, 8080);
+    assert!(config.verbose);
+
+    let config = load(dir.path(), &["myapp", "--dry-run", "--verbose"])?;
+    assert_eq!(config.port, 80);
+    assert!(config.verbose);
+    Ok(())
+}
+
+#[test]
+fn invalid_values_of_known_args_still_fail() -> Result<(), Box<dyn std::error::Error>> {
+    if let (Some(port), Some(dir)) = (env::var_os(CHILD_PORT), env::var_os(CHILD_DIR)) {
+        let port = port.to_str().ok_or("port isn't UTF-8")?;
+        load(
+            Path::new(&dir),
+            &["myapp", "--tracing-level", "debug", "--port", port],
+        )?;
+        panic!("--port {port} should exit with a usage error");
+    }
+
+    let dir = tempfile::tempdir()?;
+    fs::write(dir.path().join("config.toml"), "port = 80")?;
+
+    for port in ["abc", "70000"] {
+        let output = Command::new(env::current_exe()?)
+            .args([
+                "--exact",
+                "invalid_values_of_known_args_still_fail",
+                "--nocapture",
+            ])
+            .env(CHILD_PORT, port)
+            .env(CHILD_DIR, dir.path())
+            .output()?;
+        assert_eq!(output.status.code(), Some(2), "{port}: {output:?}");
+
+        let stderr = String::from_utf8(output.stderr)?;
+        assert!(
+            stderr.contains(&format!("invalid value '{port}' for '--port <PORT>'")),
+            "{port}: {stderr}"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+// Copyright (c) 2025 kingananas20
+
+//! YAML files with each of the YAML backends.
+//!
+//! Run with `--no-default-features --features yaml-serde_yml` for the `serde_yml` backend and
+//! with `--no-default-features` for no backend.
+
+use konfik::{ConfigLoader, Error, Konfik, Nested};
+use std::fs;
+
+#[derive(serde::Deserialize, Konfik)]
+#[cfg_attr(
+    not(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml")),
+    expect(dead_code)
+)]
+struct AppConfig {
+    name: String,
+    #[konfik(nested)]
+    server: Server,
+}
+
+#[derive(serde::Deserialize, Nested)]
+#[cfg_attr(
+    not(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml")),
+    expect(dead_code)
+)]
+struct Server {
+    port: u16,
+    hosts: Vec<String>,
+}
+
+const CONFIG: &str = "name: konfik\nserver:\n  port: 8080\n  hosts: [a, b]\n";
+
+#[cfg(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml"))]
+#[test]
+fn yaml_file_is_loaded() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, CONFIG)?;
+
+    let config = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>()?;
+
+    assert_eq!(config.name, "konfik");
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.server.hosts, ["a", "b"]);
+    Ok(())
+}
+
+#[cfg(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml"))]
+#[test]
+fn malformed_yaml_fails_with_a_yaml_error() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, "name: [konfik\n")?;
+
+    let result = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>();
+
+    let Err(Error::File { source, .. }) = result else {
+        panic!("expected a file error");
+    };
+    assert!(matches!(*source, Error::Yaml(_)));
+    Ok(())
+}
+
+#[cfg(feature = "yaml-serde_yaml")]
+#[test]
+fn serde_yaml_errors_convert() {
+    let Err(e) = serde_yaml::from_str::<u16>("not a number") else {
+        panic!("expected a serde_yaml error");
+    };
+    let message = e.to_string();
+
+    let error = Error::from(e);
+
+    assert!(matches!(&error, Error::Yaml(_)));
+    assert_eq!(error.to_string(), format!("YAML error: {message}"));
+}
+
+#[cfg(feature = "yaml-serde_yml")]
+#[test]
+fn serde_yml_errors_convert() {
+    let Err(e) = serde_yml::from_str::<u16>("not a number") else {
+        panic!("expected a serde_yml error");
+    };
+
+    assert!(matches!(Error::from(e), Error::Yaml(_)));
+}
+
+#[cfg(not(any(feature = "yaml-serde_yaml", feature = "yaml-serde_yml")))]
+#[test]
+fn yaml_without_backend_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.yaml");
+    fs::write(&path, CONFIG)?;
+
+    let result = ConfigLoader::default()
+        .with_config_file(&path)
+        .load::<AppConfig>();
+
+    let Err(Error::File { source, .. }) = result else {
+        panic!("expected a file error");
+    };
+    assert!(source.to_string().contains("no YAML backend enabled"));
+    Ok(())
+}